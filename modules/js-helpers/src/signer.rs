@@ -1,82 +1,192 @@
-use cosmos_sdk_proto::cosmos::tx::v1beta1::{BroadcastMode, Fee};
-use crw_client::{client::get_node_info, client::ChainClient};
-use crw_types::{any_wrapper::AnyWrapper, msg::Msg};
-use crw_wallet::{crypto::Wallet, crypto::WalletJs};
+//! wasm-bindgen bridge tying [crw_wallet], [crw_client] and [types] together for browser callers:
+//! sign a message with a mnemonic-derived wallet and broadcast it to a full node over Tendermint
+//! JSON-RPC, the way the ethers-rs and iota-sdk wasm bindings expose a full sign-and-send flow
+//! rather than stopping at signing.
+
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Fee;
+use crw_client::transport::{ChainClient, TxLookup};
+use crw_client::tx::TxBuilder;
+use crw_client::CosmosError;
+use crw_types::any_wrapper::AnyWrapper;
+use crw_wallet::crypto::MnemonicWallet;
 use prost_types::Any;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use types::msg::Msg;
 use wasm_bindgen::prelude::*;
 
-/// Sign and send a transaction with the given wallet
-#[wasm_bindgen]
+/// The JS-facing shape of a wallet: just enough to re-derive a [MnemonicWallet], since the wallet
+/// itself doesn't round-trip through JSON.
+#[derive(Deserialize)]
+struct WalletJs {
+    mnemonic: String,
+    derivation_path: String,
+}
+
+/// What [sign_and_send_msg] reports back to JS: the mempool's immediate response, or - once
+/// [poll_for_commit] has confirmed it - the committed result, with the block `height` it landed
+/// in.
+#[derive(Serialize)]
+struct BroadcastOutcome {
+    txhash: String,
+    code: u32,
+    height: Option<i64>,
+    raw_log: String,
+}
+
+/// Starting interval [poll_for_commit] waits between [ChainClient::get_tx] calls.
+const INITIAL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Cap the backoff in [poll_for_commit] doubles up to, so a slow node is polled at a steady rate
+/// rather than ever more rarely.
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How long [poll_for_commit] waits for a transaction to be included in a block before giving up.
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Signs `js_msg` with the wallet described by `js_wallet` and broadcasts it to `rpc_addr`'s
+/// Tendermint JSON-RPC endpoint.
+///
+/// The mempool only accepts or rejects a transaction; it isn't necessarily included in a block
+/// yet by the time this returns. Pass `wait_for_commit: true` to additionally poll for that with
+/// [poll_for_commit], so the caller learns the transaction's real on-chain `code`/`height` instead
+/// of just the mempool's.
+#[wasm_bindgen(js_name = signAndSendMsg)]
 #[allow(dead_code)]
 pub async fn sign_and_send_msg(
     js_wallet: JsValue,
+    hrp: String,
     js_msg: JsValue,
-    js_fees: JsValue,
+    js_fee: JsValue,
     memo: String,
-    lcd_addr: String,
-    grpc_addr: String,
+    chain_id: String,
+    rpc_addr: String,
+    wait_for_commit: bool,
 ) -> Result<JsValue, JsValue> {
-    // convert all the js values to actual rust types
-    let wallet_js: WalletJs = js_wallet.into_serde().unwrap();
-    let wallet = Wallet::from(wallet_js);
-    let wrapped_msg: AnyWrapper = js_msg.into_serde().unwrap();
-    let msg: Msg = Msg(Any::from(wrapped_msg));
-    let wrapped_fee: AnyWrapper = js_fees.into_serde().unwrap();
-    let fees: Fee = Fee::from(wrapped_fee);
-
-    let response = get_node_info(lcd_addr.to_string())
+    let wallet_js: WalletJs = js_wallet.into_serde().map_err(to_js_error)?;
+    let wallet = MnemonicWallet::new(&wallet_js.mnemonic, &wallet_js.derivation_path)
+        .map_err(to_js_error)?;
+    let address = wallet.get_bech32_address(&hrp).map_err(to_js_error)?;
+
+    let msg: Msg = Any::from(js_msg.into_serde::<AnyWrapper>().map_err(to_js_error)?).into();
+    let fee: Fee = js_fee
+        .into_serde::<AnyWrapper>()
+        .map_err(to_js_error)?
+        .into();
+
+    let chain_client = ChainClient::new(&rpc_addr);
+    let account = chain_client
+        .get_account_data(&address)
         .await
-        .map_err(|error| JsValue::from_serde(&error).unwrap())?;
+        .map_err(to_js_error)?;
+
+    let tx = TxBuilder::new(&chain_id)
+        .memo(&memo)
+        .account_info(account.sequence, account.account_number)
+        .fee_raw(fee)
+        .add_any_message(msg.into())
+        .sign(&wallet)
+        .map_err(to_js_error)?;
+
+    let broadcast = chain_client
+        .broadcast_tx_sync(&tx)
+        .await
+        .map_err(to_js_error)?;
+
+    let confirmed = if broadcast.code == 0 && wait_for_commit {
+        poll_for_commit(&chain_client, &broadcast.tx_hash)
+            .await
+            .map_err(to_js_error)?
+    } else {
+        None
+    };
+
+    let outcome = match confirmed {
+        Some(tx) => BroadcastOutcome {
+            txhash: broadcast.tx_hash,
+            code: tx.code,
+            height: Some(tx.height),
+            raw_log: tx.raw_log,
+        },
+        None => BroadcastOutcome {
+            txhash: broadcast.tx_hash,
+            code: broadcast.code,
+            height: None,
+            raw_log: broadcast.raw_log,
+        },
+    };
+
+    JsValue::from_serde(&outcome).map_err(to_js_error)
+}
 
-    let chain_client = ChainClient::new(
-        response.node_info.clone(),
-        lcd_addr.to_string(),
-        grpc_addr.to_string(),
-    );
+/// Polls [ChainClient::get_tx] for `hash`, backing off from [INITIAL_POLL_INTERVAL] towards
+/// [MAX_POLL_INTERVAL] between attempts, until a result is returned or [CONFIRMATION_TIMEOUT]
+/// elapses. Returns `Ok(None)` on timeout rather than an error, since the transaction may still
+/// land later and the caller already has its hash to look up again.
+async fn poll_for_commit(
+    chain_client: &ChainClient,
+    hash: &str,
+) -> Result<Option<TxLookup>, CosmosError> {
+    let mut interval = INITIAL_POLL_INTERVAL;
+    let mut waited = Duration::ZERO;
+
+    loop {
+        if let Some(tx) = chain_client.get_tx(hash).await? {
+            return Ok(Some(tx));
+        }
+
+        if waited >= CONFIRMATION_TIMEOUT {
+            return Ok(None);
+        }
+
+        sleep(interval).await;
+        waited += interval;
+        interval = (interval * 2).min(MAX_POLL_INTERVAL);
+    }
+}
 
-    let account = chain_client
-        .get_account_data(wallet.bech32_address.clone())
-        .await
-        .map_err(|error| JsValue::from_serde(&error).unwrap())?;
-
-    let msgs = vec![msg];
-    let signed_bytes = wallet
-        .sign_tx(
-            account,
-            chain_client.node_info.network.clone(),
-            msgs,
-            fees,
-            Some(memo.to_string()),
-            0,
-        )
-        .map_err(|error| JsValue::from_serde(&error).unwrap())?;
+/// Suspends the current task for `duration`, by scheduling a `setTimeout` callback and awaiting
+/// it as a [wasm_bindgen_futures::JsFuture] - there's no OS timer to hand off to in a browser.
+async fn sleep(duration: Duration) {
+    let millis = duration.as_millis() as i32;
 
-    let result = chain_client
-        .broadcast_tx(signed_bytes, BroadcastMode::Block)
-        .await
-        .map_err(|error| JsValue::from_serde(&error).unwrap())?;
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let window = web_sys::window().expect("sign_and_send_msg only runs in a browser window");
+        window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis)
+            .expect("setTimeout is always available on window");
+    });
 
-    Ok(JsValue::from_serde(&result.txhash).unwrap())
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+fn to_js_error<E: std::fmt::Display>(err: E) -> JsValue {
+    JsValue::from(err.to_string())
 }
 
 /// Import a wallet from the given mnemonic
-#[wasm_bindgen]
+#[wasm_bindgen(js_name = importWallet)]
 #[allow(dead_code)]
 pub fn import_wallet(mnemonic: &str, derivation_path: &str, hrp: &str) -> Result<JsValue, JsValue> {
-    let wallet: WalletJs =
-        Wallet::from_mnemonic(mnemonic, derivation_path.to_string(), hrp.to_string())
-            .map_err(|error| JsValue::from_serde(&error).unwrap())?
-            .into();
+    let wallet = MnemonicWallet::new(mnemonic, derivation_path).map_err(to_js_error)?;
 
-    Ok(JsValue::from_serde(&wallet).unwrap())
+    let info = WalletInfo {
+        bech32_address: wallet.get_bech32_address(hrp).map_err(to_js_error)?,
+        public_key: hex::encode(wallet.get_pub_key().to_bytes()),
+    };
 
+    JsValue::from_serde(&info).map_err(to_js_error)
+}
 
+/// The JS-facing shape returned by [import_wallet]: enough to address and identify the wallet,
+/// without handing the mnemonic it was derived from back out.
+#[derive(Serialize, Deserialize)]
+struct WalletInfo {
+    bech32_address: String,
+    public_key: String,
 }
 
 #[cfg(test)]
 mod test {
-    use crate::signer::import_wallet;
-    use crw_wallet::crypto::WalletJs;
+    use crate::signer::{import_wallet, WalletInfo};
     use wasm_bindgen_test::*;
     wasm_bindgen_test_configure!(run_in_browser);
 
@@ -85,18 +195,19 @@ mod test {
         let js_wallet = import_wallet(
             "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
             "m/44'/852'/0'/0/0",
-            "desmos"
-        ).unwrap();
+            "desmos",
+        )
+        .unwrap();
 
-        let wallet_js: WalletJs = js_wallet.into_serde().unwrap();
+        let wallet_info: WalletInfo = js_wallet.into_serde().unwrap();
 
         assert_eq!(
-            wallet_js.bech32_address.as_str(),
+            wallet_info.bech32_address.as_str(),
             "desmos1k8u92hx3k33a5vgppkyzq6m4frxx7ewnlkyjrh"
         );
 
         assert_eq!(
-            wallet_js.public_key.as_str(),
+            wallet_info.public_key.as_str(),
             "02f5bf794ef934cb419bb9113f3a94c723ec6c2881a8d99eef851fd05b61ad698d"
         )
     }
@@ -6,26 +6,152 @@ use crate::error::CosmosError;
 use crate::json::{NodeInfo, NodeInfoResponse};
 use cosmos_sdk_proto::cosmos::{
     auth::v1beta1::{query_client::QueryClient, BaseAccount, QueryAccountRequest},
-    base::abci::v1beta1::TxResponse,
-    tx::v1beta1::{service_client::ServiceClient, BroadcastMode, BroadcastTxRequest, Tx, TxRaw},
+    base::{
+        abci::v1beta1::{GasInfo, TxResponse},
+        v1beta1::Coin,
+    },
+    tx::v1beta1::{
+        service_client::ServiceClient, BroadcastMode, BroadcastTxRequest, Fee, GetTxRequest,
+        SimulateRequest, Tx, TxRaw,
+    },
 };
 use reqwest::{get, StatusCode};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
 use tonic::codegen::http::uri::InvalidUri;
 use tonic::transport::Endpoint;
-use tonic::{codegen::http::Uri, transport::Channel, Request};
+use tonic::{codegen::http::Uri, transport::Channel, Code, Request};
+
+/// How long a [NodeEndpoint] is skipped for after it fails a call, before [CosmosClient] gives it
+/// another chance.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Lazily establishes the gRPC [Channel] to a node and caches it so every query/broadcast reuses
+/// the same TCP/HTTP2 connection instead of reconnecting on every call. [ChannelCache::invalidate]
+/// drops a cached channel that turned out to be broken, so the next [ChannelCache::get]
+/// transparently re-establishes it.
+struct ChannelCache {
+    endpoint: Endpoint,
+    channel: RwLock<Option<Channel>>,
+}
+
+impl ChannelCache {
+    fn new(endpoint: Endpoint) -> ChannelCache {
+        ChannelCache {
+            endpoint,
+            channel: RwLock::new(None),
+        }
+    }
+
+    /// Returns the cached [Channel], connecting one first if none has been established yet.
+    async fn get(&self) -> Result<Channel, CosmosError> {
+        if let Some(channel) = self.channel.read().await.clone() {
+            return Ok(channel);
+        }
+
+        let mut channel = self.channel.write().await;
+        // Another task may have connected while we were waiting for the write lock.
+        if let Some(channel) = channel.as_ref() {
+            return Ok(channel.clone());
+        }
+
+        let connected = self
+            .endpoint
+            .connect()
+            .await
+            .map_err(|err| CosmosError::Grpc(err.to_string()))?;
+        *channel = Some(connected.clone());
+
+        Ok(connected)
+    }
+
+    /// Drops the cached channel, if any, so the next [ChannelCache::get] reconnects.
+    async fn invalidate(&self) {
+        *self.channel.write().await = None;
+    }
+
+    /// Runs `f` against the cached channel, invalidating it and retrying once against a freshly
+    /// established connection if the call fails (e.g. because the node closed an idle
+    /// connection).
+    async fn call<T, F, Fut>(&self, f: F) -> Result<T, CosmosError>
+    where
+        F: Fn(Channel) -> Fut,
+        Fut: Future<Output = Result<T, tonic::Status>>,
+    {
+        let channel = self.get().await?;
+        if let Ok(value) = f(channel).await {
+            return Ok(value);
+        }
+
+        self.invalidate().await;
+        let channel = self.get().await?;
+        f(channel)
+            .await
+            .map_err(|err| CosmosError::Grpc(err.to_string()))
+    }
+}
+
+/// One of the gRPC/LCD endpoint pairs a [CosmosClient] can talk to. Tracks its own health
+/// independently of the others, so one unreachable node doesn't affect queries served by the
+/// rest.
+struct NodeEndpoint {
+    lcd_addr: String,
+    grpc_addr: String,
+    channel_cache: ChannelCache,
+    /// Set after a failed call; cleared once the endpoint answers successfully again. While in
+    /// the future, [CosmosClient::with_failover] skips this endpoint in favor of a healthy one.
+    cooldown_until: RwLock<Option<Instant>>,
+}
+
+impl NodeEndpoint {
+    fn new(lcd_addr: &str, grpc_addr: &str) -> Result<NodeEndpoint, InvalidUri> {
+        let grpc_uri = grpc_addr.parse::<Uri>()?;
+
+        Ok(NodeEndpoint {
+            lcd_addr: lcd_addr.to_owned(),
+            grpc_addr: grpc_addr.to_owned(),
+            channel_cache: ChannelCache::new(Channel::builder(grpc_uri)),
+            cooldown_until: RwLock::new(None),
+        })
+    }
+
+    async fn in_cooldown(&self) -> bool {
+        matches!(*self.cooldown_until.read().await, Some(until) if Instant::now() < until)
+    }
+
+    async fn mark_failed(&self, cooldown: Duration) {
+        *self.cooldown_until.write().await = Some(Instant::now() + cooldown);
+    }
+
+    async fn mark_healthy(&self) {
+        *self.cooldown_until.write().await = None;
+    }
+}
 
 /// Client to communicate with a full node.
 #[derive(Clone)]
 pub struct CosmosClient {
-    grpc_channel: Endpoint,
-    lcd_addr: String,
+    /// Ordered list of endpoints this client fails over across; never empty.
+    endpoints: Arc<Vec<NodeEndpoint>>,
+    /// Index, into `endpoints`, of the endpoint that last answered successfully (or `0`, if none
+    /// has been tried yet). Calls start here and fail over forward from it.
+    current: Arc<AtomicUsize>,
+    cooldown: Duration,
 }
 
 impl CosmosClient {
-    /// Create a new CosmosClient instance that communicates with the full node.  
+    /// Create a new CosmosClient instance that communicates with a single full node.
     /// The client will communicate using `lcd_address` for the legacy LCD requests
     /// and `grpc_addr` for the new gRPC request.
     ///
+    /// The gRPC channel isn't connected yet at this point: it's established lazily, on the first
+    /// query or broadcast, and then cached for reuse by every [CosmosClient] clone.
+    ///
     /// # Errors
     /// Returns an [`Err`] if `grpc_addr` is an invalid URI.
     ///
@@ -37,57 +163,154 @@ impl CosmosClient {
     ///  let client = CosmosClient::new("http://localhost:1317", "http://localhost:9090").unwrap();
     /// ```
     pub fn new(lcd_addr: &str, grpc_addr: &str) -> Result<CosmosClient, InvalidUri> {
-        let grpc_uri = grpc_addr.parse::<Uri>()?;
-        let grpc_channel = Channel::builder(grpc_uri);
+        CosmosClient::new_with_endpoints(&[(lcd_addr, grpc_addr)])
+    }
+
+    /// Create a new CosmosClient instance that fails over across several full nodes.
+    ///
+    /// `endpoints` is an ordered list of `(lcd_addr, grpc_addr)` pairs; calls start at the first
+    /// one and move on to the next whenever the current one is unreachable, automatically
+    /// retrying the failed one later (see [DEFAULT_COOLDOWN]).
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if any `grpc_addr` is an invalid URI, or if `endpoints` is empty.
+    pub fn new_with_endpoints(
+        endpoints: &[(&str, &str)],
+    ) -> Result<CosmosClient, InvalidUri> {
+        if endpoints.is_empty() {
+            // `InvalidUri` has no public constructor; parsing an empty URI yields its "empty" variant.
+            return Err("".parse::<Uri>().unwrap_err());
+        }
+
+        let endpoints = endpoints
+            .iter()
+            .map(|(lcd_addr, grpc_addr)| NodeEndpoint::new(lcd_addr, grpc_addr))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(CosmosClient {
-            grpc_channel,
-            lcd_addr: lcd_addr.to_string(),
+            endpoints: Arc::new(endpoints),
+            current: Arc::new(AtomicUsize::new(0)),
+            cooldown: DEFAULT_COOLDOWN,
         })
     }
 
-    /// Gets the information of a full node.
-    pub async fn node_info(&self) -> Result<NodeInfo, CosmosError> {
-        let endpoint = format!("{}{}", self.lcd_addr, "/node_info");
-        let response = get(&endpoint)
-            .await
-            .map_err(|err| CosmosError::Lcd(err.to_string()))?;
+    /// The LCD address of the endpoint a call would try first right now, i.e. the one that
+    /// answered the last call successfully (or the first configured one, before any call has
+    /// succeeded). A call on which every endpoint fails leaves it unchanged.
+    pub fn current_endpoint(&self) -> &str {
+        &self.endpoints[self.current.load(Ordering::SeqCst)].lcd_addr
+    }
+
+    /// The gRPC address of the endpoint a call would try first right now; see
+    /// [CosmosClient::current_endpoint] for the LCD counterpart.
+    pub fn current_grpc_endpoint(&self) -> &str {
+        &self.endpoints[self.current.load(Ordering::SeqCst)].grpc_addr
+    }
 
-        let node_info_response: NodeInfoResponse;
-        match response.status() {
-            StatusCode::OK => {
-                // Unwrap here is safe since we already knew that the response is good
-                node_info_response = response.json::<NodeInfoResponse>().await.unwrap()
+    /// Runs `f` against each configured endpoint, in order starting from [CosmosClient::current_endpoint],
+    /// skipping any still in cooldown, until one succeeds. The endpoint that succeeds becomes the
+    /// new [CosmosClient::current_endpoint]; one that fails is put on cooldown. If every endpoint is
+    /// in cooldown, they're all tried anyway rather than giving up outright.
+    async fn with_failover<T, F, Fut>(&self, f: F) -> Result<T, CosmosError>
+    where
+        F: Fn(usize) -> Fut,
+        Fut: Future<Output = Result<T, CosmosError>>,
+    {
+        let len = self.endpoints.len();
+        let start = self.current.load(Ordering::SeqCst) % len;
+
+        let mut last_err = None;
+        let mut tried_any = false;
+
+        for offset in 0..len {
+            let idx = (start + offset) % len;
+            if self.endpoints[idx].in_cooldown().await {
+                continue;
+            }
+            tried_any = true;
+
+            match f(idx).await {
+                Ok(value) => {
+                    self.endpoints[idx].mark_healthy().await;
+                    self.current.store(idx, Ordering::SeqCst);
+                    return Ok(value);
+                }
+                Err(err) => {
+                    self.endpoints[idx].mark_failed(self.cooldown).await;
+                    last_err = Some(err);
+                }
             }
-            status_code => return Err(CosmosError::Lcd(status_code.to_string())),
         }
 
-        Ok(node_info_response.node_info)
+        if !tried_any {
+            for offset in 0..len {
+                let idx = (start + offset) % len;
+                match f(idx).await {
+                    Ok(value) => {
+                        self.endpoints[idx].mark_healthy().await;
+                        self.current.store(idx, Ordering::SeqCst);
+                        return Ok(value);
+                    }
+                    Err(err) => last_err = Some(err),
+                }
+            }
+        }
+
+        Err(last_err.expect("endpoints is never empty, so at least one call was attempted"))
     }
 
-    /// Returns the account data associated to the given address.
-    pub async fn get_account_data(&self, address: &str) -> Result<BaseAccount, CosmosError> {
-        // Create channel connection to the gRPC server
-        let channel = self
-            .grpc_channel
-            .connect()
-            .await
-            .map_err(|err| CosmosError::Grpc(err.to_string()))?;
+    /// Gets the information of a full node, failing over across endpoints like every other call.
+    pub async fn node_info(&self) -> Result<NodeInfo, CosmosError> {
+        self.with_failover(|idx| async move {
+            let url = format!("{}/node_info", self.endpoints[idx].lcd_addr);
+            let response = get(&url).await.map_err(|err| CosmosError::Lcd(err.to_string()))?;
+
+            match response.status() {
+                StatusCode::OK => response
+                    .json::<NodeInfoResponse>()
+                    .await
+                    .map(|r| r.node_info)
+                    .map_err(|err| CosmosError::Lcd(err.to_string())),
+                status_code => Err(CosmosError::Lcd(status_code.to_string())),
+            }
+        })
+        .await
+    }
 
-        // Create gRPC query auth client from channel
-        let mut client = QueryClient::new(channel);
+    /// Clears every configured endpoint's cooldown, so each gets an immediate fresh chance, then
+    /// makes one lightweight [CosmosClient::node_info] call. That call tries endpoints in order
+    /// from [CosmosClient::current_endpoint] and stops at the first one that answers, so only
+    /// that endpoint's [NodeInfo] is returned; the other endpoints are not queried and their
+    /// chain-id/network are not compared.
+    pub async fn refresh_node_info(&self) -> Result<NodeInfo, CosmosError> {
+        for endpoint in self.endpoints.iter() {
+            endpoint.mark_healthy().await;
+        }
 
-        // Build a new request
-        let request = Request::new(QueryAccountRequest {
-            address: address.to_owned(),
-        });
+        self.node_info().await
+    }
 
-        // Send request and wait for response
-        let response = client
-            .account(request)
-            .await
-            .map_err(|err| CosmosError::Grpc(err.to_string()))?
-            .into_inner();
+    /// Returns the account data associated to the given address.
+    pub async fn get_account_data(&self, address: &str) -> Result<BaseAccount, CosmosError> {
+        let response = self
+            .with_failover(|idx| {
+                let address = address.to_owned();
+                async move {
+                    self.endpoints[idx]
+                        .channel_cache
+                        .call(|channel| {
+                            let address = address.clone();
+                            async move {
+                                QueryClient::new(channel)
+                                    .account(Request::new(QueryAccountRequest { address }))
+                                    .await
+                                    .map(|response| response.into_inner())
+                            }
+                        })
+                        .await
+                }
+            })
+            .await?;
 
         // Decode response body into BaseAccount
         let base_account: BaseAccount =
@@ -102,48 +325,237 @@ impl CosmosClient {
         tx: &Tx,
         mode: BroadcastMode,
     ) -> Result<Option<TxResponse>, CosmosError> {
-        // Some buffers used to serialize the objects
-        let mut serialized_body: Vec<u8> = Vec::new();
-        let mut serialized_auth: Vec<u8> = Vec::new();
-        let mut serialized_tx: Vec<u8> = Vec::new();
-
-        // Serialize the tx body and auth_info
-        if let Some(body) = &tx.body {
-            prost::Message::encode(body, &mut serialized_body)?;
+        let serialized_tx = encode_tx_raw(tx)?;
+
+        let response = self
+            .with_failover(|idx| {
+                let tx_bytes = serialized_tx.clone();
+                async move {
+                    self.endpoints[idx]
+                        .channel_cache
+                        .call(|channel| {
+                            let tx_bytes = tx_bytes.clone();
+                            async move {
+                                ServiceClient::new(channel)
+                                    .broadcast_tx(Request::new(BroadcastTxRequest {
+                                        tx_bytes,
+                                        mode: mode as i32,
+                                    }))
+                                    .await
+                                    .map(|response| response.into_inner())
+                            }
+                        })
+                        .await
+                }
+            })
+            .await?;
+
+        Ok(response.tx_response)
+    }
+
+    /// Looks up a transaction by its hex-encoded hash via the Cosmos SDK `GetTx` gRPC query,
+    /// failing over across endpoints like every other call. Returns `Ok(None)` if the node doesn't
+    /// know about the hash yet, rather than treating that as an error, since that's the expected
+    /// state while [CosmosClient::broadcast_tx_commit] polls for inclusion.
+    pub async fn get_tx(&self, hash: &str) -> Result<Option<TxResponse>, CosmosError> {
+        self.with_failover(|idx| {
+            let hash = hash.to_owned();
+            async move {
+                self.endpoints[idx]
+                    .channel_cache
+                    .call(|channel| {
+                        let hash = hash.clone();
+                        async move {
+                            match ServiceClient::new(channel)
+                                .get_tx(Request::new(GetTxRequest { hash }))
+                                .await
+                            {
+                                Ok(response) => Ok(response.into_inner().tx_response),
+                                Err(status) if status.code() == Code::NotFound => Ok(None),
+                                Err(status) => Err(status),
+                            }
+                        }
+                    })
+                    .await
+            }
+        })
+        .await
+    }
+
+    /// Broadcasts `tx` in [BroadcastMode::Sync] and then polls [CosmosClient::get_tx] for the
+    /// resulting hash until it's included in a block, returning the committed [TxResponse] with
+    /// its real `code`/`raw_log`/`height`. Gives up with [CosmosError::Timeout] if `timeout`
+    /// elapses first, checking every `poll_interval`.
+    ///
+    /// Sync mode is used rather than [BroadcastMode::Block], which blocks the node itself until
+    /// the tx is committed (or its own, less controllable timeout elapses); polling client-side
+    /// gives the caller control over how long to wait instead.
+    pub async fn broadcast_tx_commit(
+        &self,
+        tx: &Tx,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<TxResponse, CosmosError> {
+        let response = self
+            .broadcast_tx(tx, BroadcastMode::Sync)
+            .await?
+            .ok_or_else(|| {
+                CosmosError::Grpc("broadcast response carried no tx_response".to_owned())
+            })?;
+
+        // A non-zero code here means the mempool already rejected the tx (e.g. a bad sequence
+        // number): it will never be included in a block, so there's nothing to poll for.
+        if is_rejected(response.code) {
+            return Ok(response);
         }
-        if let Some(auth_info) = &tx.auth_info {
-            prost::Message::encode(auth_info, &mut serialized_auth)?;
+
+        let hash = response.txhash;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(committed) = self.get_tx(&hash).await? {
+                return Ok(committed);
+            }
+
+            if Instant::now() >= deadline {
+                return Err(CosmosError::Timeout(hash));
+            }
+
+            tokio::time::sleep(poll_interval).await;
         }
+    }
 
-        // Prepare and serialize the TxRaw
-        let tx_raw = TxRaw {
-            body_bytes: serialized_body,
-            auth_info_bytes: serialized_auth,
-            signatures: tx.signatures.clone(),
-        };
-        prost::Message::encode(&tx_raw, &mut serialized_tx)?;
+    /// Simulates `tx` against the node without broadcasting it, returning the [GasInfo] the node
+    /// computed for it. `tx` only needs a placeholder fee and a syntactically valid signature:
+    /// the Cosmos SDK's simulate mode skips signature verification, so the gas it reports
+    /// reflects the messages and signer count, not the fee or signature contents.
+    pub async fn simulate_tx(&self, tx: &Tx) -> Result<GasInfo, CosmosError> {
+        let serialized_tx = encode_tx_raw(tx)?;
+
+        let response = self
+            .with_failover(|idx| {
+                let tx_bytes = serialized_tx.clone();
+                async move {
+                    self.endpoints[idx]
+                        .channel_cache
+                        .call(|channel| {
+                            let tx_bytes = tx_bytes.clone();
+                            async move {
+                                ServiceClient::new(channel)
+                                    .simulate(Request::new(SimulateRequest {
+                                        tx: None,
+                                        tx_bytes,
+                                    }))
+                                    .await
+                                    .map(|response| response.into_inner())
+                            }
+                        })
+                        .await
+                }
+            })
+            .await?;
+
+        response
+            .gas_info
+            .ok_or_else(|| CosmosError::Grpc("simulation response carried no gas_info".to_owned()))
+    }
 
-        // Open the channel and perform the actual gRPC BroadcastTxRequest
-        let channel = self
-            .grpc_channel
-            .connect()
-            .await
-            .map_err(|err| CosmosError::Grpc(err.to_string()))?;
-        let mut service = ServiceClient::new(channel);
+    /// Simulates `tx` and returns a [Fee] sized to the result, so a caller doesn't have to
+    /// hand-pick a `gas_limit` upfront: `gas_limit` is the simulated `gas_used` scaled by
+    /// `gas_adjustment` (e.g. `1.3` to leave a 30% safety margin against estimation drift), and
+    /// `amount` is `gas_limit * gas_price` of `fee_denom`. Both are rounded up so the transaction
+    /// never under-pays.
+    ///
+    /// The returned [Fee] is meant to be used to rebuild and re-sign `tx` with [crate::tx::TxBuilder]
+    /// before the final [CosmosClient::broadcast_tx] call.
+    pub async fn estimate_fee(
+        &self,
+        tx: &Tx,
+        fee_denom: &str,
+        gas_price: f64,
+        gas_adjustment: f64,
+    ) -> Result<Fee, CosmosError> {
+        let gas_info = self.simulate_tx(tx).await?;
+        compute_fee(gas_info.gas_used, gas_adjustment, gas_price, fee_denom)
+    }
+}
 
-        let request = Request::new(BroadcastTxRequest {
-            tx_bytes: serialized_tx,
-            mode: mode as i32,
-        });
+/// Whether a [TxResponse::code] means the mempool rejected the tx outright, so
+/// [CosmosClient::broadcast_tx_commit] should return it immediately instead of polling
+/// [CosmosClient::get_tx] for a hash that will never be included in a block.
+fn is_rejected(code: u32) -> bool {
+    code != 0
+}
 
-        let response = service
-            .broadcast_tx(request)
-            .await
-            .map_err(|e| CosmosError::Grpc(e.to_string()))?
-            .into_inner();
+/// Sizes a [Fee] from a simulated `gas_used`: `gas_limit` is `gas_used` scaled by `gas_adjustment`
+/// (e.g. `1.3` to leave a 30% safety margin against estimation drift), and the fee `amount` is
+/// `gas_limit * gas_price` of `fee_denom`. Both are rounded up so the transaction never under-pays.
+///
+/// The arithmetic runs on [Decimal] with `checked_mul`, rather than integers or floats, so huge
+/// gas values and tiny gas prices stay exact instead of silently losing precision; any overflow
+/// is reported as [CosmosError::Overflow] instead of panicking.
+fn compute_fee(
+    gas_used: u64,
+    gas_adjustment: f64,
+    gas_price: f64,
+    fee_denom: &str,
+) -> Result<Fee, CosmosError> {
+    let gas_used = Decimal::from(gas_used);
+    let gas_adjustment = Decimal::from_f64(gas_adjustment)
+        .ok_or_else(|| CosmosError::Overflow("gas_adjustment is not a finite number".to_owned()))?;
+    let gas_price = Decimal::from_f64(gas_price)
+        .ok_or_else(|| CosmosError::Overflow("gas_price is not a finite number".to_owned()))?;
+
+    let gas_limit = gas_used
+        .checked_mul(gas_adjustment)
+        .ok_or_else(|| CosmosError::Overflow("gas_used * gas_adjustment overflowed".to_owned()))?
+        .ceil();
+    let amount = gas_limit
+        .checked_mul(gas_price)
+        .ok_or_else(|| CosmosError::Overflow("gas_limit * gas_price overflowed".to_owned()))?
+        .ceil();
+
+    let gas_limit = gas_limit
+        .to_u64()
+        .ok_or_else(|| CosmosError::Overflow("gas_limit does not fit in a u64".to_owned()))?;
+    let amount = amount
+        .to_u64()
+        .ok_or_else(|| CosmosError::Overflow("fee amount does not fit in a u64".to_owned()))?;
+
+    Ok(Fee {
+        amount: vec![Coin {
+            denom: fee_denom.to_owned(),
+            amount: amount.to_string(),
+        }],
+        gas_limit,
+        payer: "".to_owned(),
+        granter: "".to_owned(),
+    })
+}
 
-        Ok(response.tx_response)
+/// Encodes `tx` into the raw `TxRaw` bytes expected by the `BroadcastTx`/`Simulate` gRPC methods
+/// (and, identically, by the Tendermint JSON-RPC `broadcast_tx_*`/`tx` methods used by
+/// [crate::transport::JsonRpcTransport]).
+pub(crate) fn encode_tx_raw(tx: &Tx) -> Result<Vec<u8>, CosmosError> {
+    let mut serialized_body: Vec<u8> = Vec::new();
+    let mut serialized_auth: Vec<u8> = Vec::new();
+    let mut serialized_tx: Vec<u8> = Vec::new();
+
+    if let Some(body) = &tx.body {
+        prost::Message::encode(body, &mut serialized_body)?;
+    }
+    if let Some(auth_info) = &tx.auth_info {
+        prost::Message::encode(auth_info, &mut serialized_auth)?;
     }
+
+    let tx_raw = TxRaw {
+        body_bytes: serialized_body,
+        auth_info_bytes: serialized_auth,
+        signatures: tx.signatures.clone(),
+    };
+    prost::Message::encode(&tx_raw, &mut serialized_tx)?;
+
+    Ok(serialized_tx)
 }
 
 #[cfg(test)]
@@ -207,4 +619,33 @@ mod tests {
         print!("{}", res.raw_log);
         assert_eq!(0, res.code);
     }
+
+    #[test]
+    fn compute_fee_rounds_up_gas_limit_and_amount() {
+        let fee = compute_fee(100_001, 1.3, 0.025, "stake").unwrap();
+
+        assert_eq!(130002, fee.gas_limit);
+        assert_eq!("stake", fee.amount[0].denom);
+        assert_eq!("3251", fee.amount[0].amount);
+    }
+
+    #[test]
+    fn compute_fee_handles_tiny_gas_prices_exactly() {
+        let fee = compute_fee(1, 1.0, 0.000001, "stake").unwrap();
+
+        assert_eq!(1, fee.gas_limit);
+        assert_eq!("1", fee.amount[0].amount);
+    }
+
+    #[test]
+    fn compute_fee_rejects_a_non_finite_gas_adjustment() {
+        let result = compute_fee(100_000, f64::NAN, 0.025, "stake");
+        assert!(matches!(result, Err(CosmosError::Overflow(_))));
+    }
+
+    #[test]
+    fn is_rejected_treats_only_nonzero_codes_as_terminal() {
+        assert!(!is_rejected(0));
+        assert!(is_rejected(1));
+    }
 }
@@ -18,6 +18,18 @@ pub enum CosmosError {
 
     #[error("LCD error: {0}")]
     Lcd(String),
+
+    #[error("timed out waiting for tx `{0}` to be included in a block")]
+    Timeout(String),
+
+    #[error("websocket error: {0}")]
+    Websocket(String),
+
+    #[error("decimal overflow computing fee: {0}")]
+    Overflow(String),
+
+    #[error("JSON-RPC error: {0}")]
+    JsonRpc(String),
 }
 
 /// The various error that can be raised from [`super::tx::TxBuilder`].
@@ -26,6 +38,9 @@ pub enum TxBuildError {
     #[error("Encoding error: {0}")]
     Encode(String),
 
+    #[error("Decoding error: {0}")]
+    Decode(String),
+
     #[error("Missing account information")]
     NoAccountInfo,
 
@@ -34,6 +49,27 @@ pub enum TxBuildError {
 
     #[error("Sign error: {0}")]
     Sign(String),
+
+    #[error("Verification error: {0}")]
+    Verify(String),
+
+    #[error("not enough signatures to satisfy the multisig threshold: {0}")]
+    NotEnoughSignatures(String),
+
+    #[error("gas simulation error: {0}")]
+    Simulate(String),
+
+    #[error("decimal overflow computing fee: {0}")]
+    Overflow(String),
+
+    #[error("{0}")]
+    MismatchedAminoMessageCount(String),
+}
+
+impl From<CosmosError> for TxBuildError {
+    fn from(e: CosmosError) -> Self {
+        TxBuildError::Simulate(e.to_string())
+    }
 }
 
 impl From<EncodeError> for CosmosError {
@@ -47,3 +83,9 @@ impl From<DecodeError> for CosmosError {
         CosmosError::Decode(e.to_string())
     }
 }
+
+impl From<DecodeError> for TxBuildError {
+    fn from(e: DecodeError) -> Self {
+        TxBuildError::Decode(e.to_string())
+    }
+}
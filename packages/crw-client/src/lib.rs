@@ -1,6 +1,8 @@
 pub mod client;
 mod error;
 pub mod json;
+pub mod subscription;
+pub mod transport;
 pub mod tx;
 
 pub use crate::error::{CosmosError, TxBuildError};
@@ -0,0 +1,301 @@
+//! Module to await transaction inclusion through a Tendermint RPC event subscription instead of
+//! polling.
+//!
+//! [CosmosClient::broadcast_tx_commit](crate::client::CosmosClient::broadcast_tx_commit) confirms
+//! a transaction by repeatedly calling `GetTx` until it's included in a block. [TxSubscriber]
+//! gives the same guarantee push-style: it opens a WebSocket connection to the node's Tendermint
+//! RPC `/websocket` endpoint, subscribes to the `Tx` event matching the broadcast transaction's
+//! hash, and resolves as soon as that event arrives.
+
+use crate::client::CosmosClient;
+use crate::error::CosmosError;
+use futures_util::{SinkExt, StreamExt};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Result of a confirmed transaction, as reported by the Tendermint `Tx` event (or, when falling
+/// back to a one-shot lookup, by [CosmosClient::get_tx]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxConfirmation {
+    /// The ABCI result code; `0` means the transaction was successfully executed.
+    pub code: u32,
+    /// The height of the block the transaction was included in.
+    pub height: i64,
+    /// The raw log emitted by the node while executing the transaction.
+    pub log: String,
+}
+
+/// Subscribes to a node's Tendermint RPC event stream to be notified of transaction inclusion,
+/// rather than polling [CosmosClient::get_tx] in a loop.
+pub struct TxSubscriber {
+    /// The Tendermint RPC WebSocket address, e.g. `ws://localhost:26657/websocket`.
+    rpc_ws_addr: String,
+}
+
+impl TxSubscriber {
+    /// Creates a new [TxSubscriber] pointed at `rpc_ws_addr`, a node's Tendermint RPC WebSocket
+    /// endpoint (e.g. `ws://localhost:26657/websocket`). The connection isn't opened until
+    /// [TxSubscriber::await_tx_commit] is called.
+    pub fn new(rpc_ws_addr: &str) -> TxSubscriber {
+        TxSubscriber {
+            rpc_ws_addr: rpc_ws_addr.to_owned(),
+        }
+    }
+
+    /// Waits for the transaction encoded as `tx_bytes` (the same bytes passed to
+    /// [CosmosClient::broadcast_tx]) to be included in a block, returning its [TxConfirmation].
+    ///
+    /// The subscription is registered by its hash (the uppercase hex SHA-256 of `tx_bytes`, which
+    /// is how Tendermint indexes `tx.hash`), so this can be called concurrently with (or even
+    /// slightly before) the broadcast itself. Two edge cases are handled transparently:
+    /// * if the transaction is already committed by the time the subscription is registered, this
+    ///   falls back to a one-shot [CosmosClient::get_tx] lookup instead of waiting for an event
+    ///   that already happened;
+    /// * if the WebSocket connection drops before a matching event arrives, it's re-established
+    ///   and the subscription is sent again.
+    ///
+    /// # Errors
+    /// Returns [CosmosError::Timeout] if `timeout` elapses with no confirmation.
+    pub async fn await_tx_commit(
+        &self,
+        cosmos_client: &CosmosClient,
+        tx_bytes: &[u8],
+        timeout: Duration,
+    ) -> Result<TxConfirmation, CosmosError> {
+        let hash = uppercase_hex_sha256(tx_bytes);
+        let deadline = Instant::now() + timeout;
+
+        // The tx may already be committed (the broadcast can race ahead of the subscription
+        // being registered), so check once upfront before paying for a WebSocket round-trip.
+        if let Some(confirmation) = self.check_committed(cosmos_client, &hash).await? {
+            return Ok(confirmation);
+        }
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(CosmosError::Timeout(hash));
+            }
+
+            // A connection that drops, or simply times out without a match, is retried: either
+            // way the event may have fired while we weren't listening, so re-check before
+            // reconnecting.
+            if let Some(confirmation) = self.subscribe_once(&hash, remaining).await? {
+                return Ok(confirmation);
+            }
+
+            if let Some(confirmation) = self.check_committed(cosmos_client, &hash).await? {
+                return Ok(confirmation);
+            }
+        }
+    }
+
+    /// Looks up `hash` via [CosmosClient::get_tx], translating a hit into a [TxConfirmation].
+    async fn check_committed(
+        &self,
+        cosmos_client: &CosmosClient,
+        hash: &str,
+    ) -> Result<Option<TxConfirmation>, CosmosError> {
+        Ok(cosmos_client
+            .get_tx(hash)
+            .await?
+            .map(|tx_response| TxConfirmation {
+                code: tx_response.code,
+                height: tx_response.height,
+                log: tx_response.raw_log,
+            }))
+    }
+
+    /// Opens one WebSocket connection, subscribes to the `Tx` event matching `hash`, and waits up
+    /// to `timeout` for a matching notification. Returns `Ok(None)` if the deadline elapses or the
+    /// connection is closed or drops without ever seeing a match; the caller decides whether to
+    /// retry.
+    async fn subscribe_once(
+        &self,
+        hash: &str,
+        timeout: Duration,
+    ) -> Result<Option<TxConfirmation>, CosmosError> {
+        let (mut socket, _) = tokio_tungstenite::connect_async(&self.rpc_ws_addr)
+            .await
+            .map_err(|err| CosmosError::Websocket(err.to_string()))?;
+
+        let subscribe_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "subscribe",
+            "id": 0,
+            "params": { "query": format!("tm.event='Tx' AND tx.hash='{hash}'") },
+        });
+        socket
+            .send(Message::Text(subscribe_request.to_string()))
+            .await
+            .map_err(|err| CosmosError::Websocket(err.to_string()))?;
+
+        let wait_for_match = async {
+            while let Some(message) = socket.next().await {
+                // A read error means the connection dropped: stop listening on it.
+                let Ok(message) = message else {
+                    break;
+                };
+                let Message::Text(text) = message else {
+                    continue;
+                };
+
+                if let Ok(response) = serde_json::from_str::<SubscriptionResponse>(&text) {
+                    if let Some(confirmation) = response.into_confirmation(hash) {
+                        return Ok(Some(confirmation));
+                    }
+                }
+            }
+            // The socket was closed or dropped before a matching event arrived.
+            Ok::<_, CosmosError>(None)
+        };
+
+        let outcome = match tokio::time::timeout(timeout, wait_for_match).await {
+            Ok(result) => result,
+            // Timed out waiting on this particular connection; the caller re-checks and retries.
+            Err(_) => Ok(None),
+        };
+
+        let _ = socket.close(None).await;
+        outcome
+    }
+}
+
+/// Returns the uppercase hex SHA-256 digest of `tx_bytes`, the hash Tendermint indexes
+/// transactions under for its `tx.hash` event attribute.
+fn uppercase_hex_sha256(tx_bytes: &[u8]) -> String {
+    hex::encode_upper(Sha256::digest(tx_bytes))
+}
+
+/// A JSON-RPC notification as sent over the `/websocket` subscription endpoint.
+#[derive(serde::Deserialize)]
+struct SubscriptionResponse {
+    result: Option<SubscriptionResult>,
+}
+
+impl SubscriptionResponse {
+    /// Extracts the [TxConfirmation] carried by this notification, if it's a `Tx` event matching
+    /// `hash`.
+    fn into_confirmation(self, hash: &str) -> Option<TxConfirmation> {
+        let result = self.result?;
+
+        let matches_hash = result
+            .events
+            .get("tx.hash")
+            .map(|values| values.iter().any(|value| value == hash))
+            .unwrap_or(false);
+        if !matches_hash {
+            return None;
+        }
+
+        let tx_result = result.data?.value.tx_result;
+        Some(TxConfirmation {
+            code: tx_result.result.code,
+            height: tx_result.height.parse().ok()?,
+            log: tx_result.result.log,
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct SubscriptionResult {
+    #[serde(default)]
+    events: HashMap<String, Vec<String>>,
+    data: Option<SubscriptionData>,
+}
+
+#[derive(serde::Deserialize)]
+struct SubscriptionData {
+    value: SubscriptionValue,
+}
+
+#[derive(serde::Deserialize)]
+struct SubscriptionValue {
+    #[serde(rename = "TxResult")]
+    tx_result: TxResultEnvelope,
+}
+
+#[derive(serde::Deserialize)]
+struct TxResultEnvelope {
+    height: String,
+    result: TxExecResult,
+}
+
+#[derive(serde::Deserialize)]
+struct TxExecResult {
+    #[serde(default)]
+    code: u32,
+    #[serde(default)]
+    log: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_uppercase_hex() {
+        let hash = uppercase_hex_sha256(b"some tx bytes");
+        assert_eq!(hash, hash.to_uppercase());
+        assert_eq!(64, hash.len());
+    }
+
+    #[test]
+    fn ignores_events_for_a_different_hash() {
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "result": {
+                "query": "tm.event='Tx'",
+                "events": { "tx.hash": ["SOME-OTHER-HASH"] },
+                "data": {
+                    "type": "tendermint/event/Tx",
+                    "value": {
+                        "TxResult": {
+                            "height": "42",
+                            "result": { "code": 0, "log": "" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let response: SubscriptionResponse = serde_json::from_value(notification).unwrap();
+        assert!(response.into_confirmation("THE-HASH-WE-WANT").is_none());
+    }
+
+    #[test]
+    fn extracts_confirmation_for_matching_hash() {
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "result": {
+                "query": "tm.event='Tx'",
+                "events": { "tx.hash": ["THE-HASH-WE-WANT"] },
+                "data": {
+                    "type": "tendermint/event/Tx",
+                    "value": {
+                        "TxResult": {
+                            "height": "42",
+                            "result": { "code": 0, "log": "[]" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let response: SubscriptionResponse = serde_json::from_value(notification).unwrap();
+        let confirmation = response.into_confirmation("THE-HASH-WE-WANT").unwrap();
+
+        assert_eq!(
+            TxConfirmation {
+                code: 0,
+                height: 42,
+                log: "[]".to_owned(),
+            },
+            confirmation
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! A pluggable [ChainTransport] abstraction so a [ChainClient] can talk to a full node either over
+//! gRPC+LCD (the existing [crate::client::CosmosClient], wrapped by [GrpcTransport]) or, for
+//! deployments that only expose the Tendermint RPC port (26657), over its JSON-RPC interface
+//! instead (see [JsonRpcTransport]).
+//!
+//! [JsonRpcTransport] is a small hand-rolled client built on `reqwest`, implementing only the
+//! handful of methods [ChainTransport] needs, in the same spirit as the hand-rolled WebSocket
+//! JSON-RPC structs in [crate::subscription] - rather than a full Tendermint RPC client.
+
+use crate::client::{self, CosmosClient};
+use crate::error::CosmosError;
+use crate::json::NodeInfo;
+use async_trait::async_trait;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::{
+    BaseAccount, QueryAccountRequest, QueryAccountResponse,
+};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{BroadcastMode, Tx};
+use reqwest::Client as HttpClient;
+use serde::{Deserialize, Serialize};
+use tonic::codegen::http::uri::InvalidUri;
+
+/// The account fields a [crate::tx::TxBuilder] needs before it can sign a transaction, as returned
+/// by any [ChainTransport].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountData {
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+/// The outcome of broadcasting a transaction, as reported by whichever [ChainTransport] sent it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastResult {
+    pub tx_hash: String,
+    pub code: u32,
+    pub raw_log: String,
+}
+
+/// A transaction looked up by hash, as reported by whichever [ChainTransport] found it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxLookup {
+    pub code: u32,
+    pub height: i64,
+    pub raw_log: String,
+}
+
+/// The full node operations a [ChainClient] needs, implemented once per wire protocol: gRPC+LCD
+/// (see [GrpcTransport]) and Tendermint JSON-RPC (see [JsonRpcTransport]).
+#[async_trait]
+pub trait ChainTransport: Send + Sync {
+    /// Equivalent to the Tendermint RPC `status` method / the Cosmos SDK LCD `/node_info`.
+    async fn status(&self) -> Result<NodeInfo, CosmosError>;
+
+    /// Equivalent to an `abci_query` against the `auth` module's account store.
+    async fn get_account_data(&self, address: &str) -> Result<AccountData, CosmosError>;
+
+    /// Equivalent to `broadcast_tx_sync`: submits `tx` to the mempool and returns as soon as it's
+    /// accepted or rejected there, without waiting for block inclusion.
+    async fn broadcast_tx_sync(&self, tx: &Tx) -> Result<BroadcastResult, CosmosError>;
+
+    /// Looks up a committed transaction by its hex-encoded hash. Returns `Ok(None)` if the node
+    /// doesn't know about it yet, rather than treating that as an error.
+    async fn get_tx(&self, hash: &str) -> Result<Option<TxLookup>, CosmosError>;
+}
+
+/// [ChainTransport] backed by the existing gRPC+LCD [CosmosClient].
+pub struct GrpcTransport {
+    client: CosmosClient,
+}
+
+impl GrpcTransport {
+    /// Wraps a [CosmosClient] talking to a single node's `lcd_addr`/`grpc_addr` pair.
+    pub fn new(lcd_addr: &str, grpc_addr: &str) -> Result<GrpcTransport, InvalidUri> {
+        Ok(GrpcTransport {
+            client: CosmosClient::new(lcd_addr, grpc_addr)?,
+        })
+    }
+}
+
+#[async_trait]
+impl ChainTransport for GrpcTransport {
+    async fn status(&self) -> Result<NodeInfo, CosmosError> {
+        self.client.node_info().await
+    }
+
+    async fn get_account_data(&self, address: &str) -> Result<AccountData, CosmosError> {
+        let account = self.client.get_account_data(address).await?;
+        Ok(AccountData {
+            account_number: account.account_number,
+            sequence: account.sequence,
+        })
+    }
+
+    async fn broadcast_tx_sync(&self, tx: &Tx) -> Result<BroadcastResult, CosmosError> {
+        let response = self
+            .client
+            .broadcast_tx(tx, BroadcastMode::Sync)
+            .await?
+            .ok_or_else(|| {
+                CosmosError::Grpc("broadcast response carried no tx_response".to_owned())
+            })?;
+
+        Ok(BroadcastResult {
+            tx_hash: response.txhash,
+            code: response.code,
+            raw_log: response.raw_log,
+        })
+    }
+
+    async fn get_tx(&self, hash: &str) -> Result<Option<TxLookup>, CosmosError> {
+        Ok(self.client.get_tx(hash).await?.map(|response| TxLookup {
+            code: response.code,
+            height: response.height,
+            raw_log: response.raw_log,
+        }))
+    }
+}
+
+/// [ChainTransport] backed by a node's Tendermint JSON-RPC endpoint (e.g.
+/// `http://localhost:26657`), for deployments that only expose that port and not a gRPC one.
+pub struct JsonRpcTransport {
+    rpc_addr: String,
+    http: HttpClient,
+}
+
+impl JsonRpcTransport {
+    /// Points a [JsonRpcTransport] at a node's Tendermint RPC address, e.g.
+    /// `http://localhost:26657`.
+    pub fn new(rpc_addr: &str) -> JsonRpcTransport {
+        JsonRpcTransport {
+            rpc_addr: rpc_addr.to_owned(),
+            http: HttpClient::new(),
+        }
+    }
+
+    /// Sends a JSON-RPC 2.0 `method` call with `params` to the node, returning the deserialized
+    /// `result` field.
+    async fn call<P: Serialize, R: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<R, CosmosError> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 0,
+            method: method.to_owned(),
+            params,
+        };
+
+        let response: JsonRpcResponse<R> = self
+            .http
+            .post(&self.rpc_addr)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| CosmosError::JsonRpc(err.to_string()))?
+            .json()
+            .await
+            .map_err(|err| CosmosError::JsonRpc(err.to_string()))?;
+
+        if let Some(error) = response.error {
+            return Err(CosmosError::JsonRpc(error.message));
+        }
+        response
+            .result
+            .ok_or_else(|| CosmosError::JsonRpc(format!("{method} response carried no result")))
+    }
+}
+
+#[async_trait]
+impl ChainTransport for JsonRpcTransport {
+    async fn status(&self) -> Result<NodeInfo, CosmosError> {
+        let status: StatusResult = self.call("status", ()).await?;
+        Ok(status.node_info)
+    }
+
+    async fn get_account_data(&self, address: &str) -> Result<AccountData, CosmosError> {
+        let request = QueryAccountRequest {
+            address: address.to_owned(),
+        };
+        let mut data = Vec::new();
+        prost::Message::encode(&request, &mut data)?;
+
+        let query: AbciQueryResult = self
+            .call(
+                "abci_query",
+                AbciQueryParams {
+                    path: "/cosmos.auth.v1beta1.Query/Account".to_owned(),
+                    data: hex::encode(data),
+                    prove: false,
+                },
+            )
+            .await?;
+
+        let value = base64::decode(&query.response.value)
+            .map_err(|err| CosmosError::JsonRpc(err.to_string()))?;
+        let response: QueryAccountResponse = prost::Message::decode(value.as_ref())?;
+        let account: BaseAccount =
+            prost::Message::decode(response.account.unwrap().value.as_ref())?;
+
+        Ok(AccountData {
+            account_number: account.account_number,
+            sequence: account.sequence,
+        })
+    }
+
+    async fn broadcast_tx_sync(&self, tx: &Tx) -> Result<BroadcastResult, CosmosError> {
+        let tx_bytes = client::encode_tx_raw(tx)?;
+
+        let result: BroadcastTxResult = self
+            .call(
+                "broadcast_tx_sync",
+                BroadcastTxParams {
+                    tx: base64::encode(tx_bytes),
+                },
+            )
+            .await?;
+
+        Ok(BroadcastResult {
+            tx_hash: result.hash,
+            code: result.code,
+            raw_log: result.log,
+        })
+    }
+
+    async fn get_tx(&self, hash: &str) -> Result<Option<TxLookup>, CosmosError> {
+        let hash_bytes = hex::decode(hash).map_err(|err| CosmosError::JsonRpc(err.to_string()))?;
+
+        let result: Result<TxResult, CosmosError> = self
+            .call(
+                "tx",
+                TxParams {
+                    hash: base64::encode(hash_bytes),
+                    prove: false,
+                },
+            )
+            .await;
+
+        match result {
+            Ok(tx_result) => Ok(Some(TxLookup {
+                code: tx_result.tx_result.code,
+                height: tx_result.height.parse().unwrap_or_default(),
+                raw_log: tx_result.tx_result.log,
+            })),
+            // The node reports an unknown hash as a JSON-RPC error rather than an empty result.
+            Err(CosmosError::JsonRpc(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// A full node, reachable either over gRPC+LCD or, for nodes that only expose the Tendermint RPC
+/// port, over JSON-RPC; selected once at construction time and then used uniformly through
+/// [ChainTransport].
+pub struct ChainClient {
+    transport: Box<dyn ChainTransport>,
+}
+
+impl ChainClient {
+    /// Connects to a node that only exposes its Tendermint RPC port (e.g. `http://localhost:26657`),
+    /// using [JsonRpcTransport].
+    pub fn new(rpc_addr: &str) -> ChainClient {
+        ChainClient {
+            transport: Box::new(JsonRpcTransport::new(rpc_addr)),
+        }
+    }
+
+    /// Connects to a node's gRPC+LCD endpoint pair, using the existing [GrpcTransport] (and, under
+    /// it, [CosmosClient]).
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if `grpc_addr` is an invalid URI.
+    pub fn new_grpc(lcd_addr: &str, grpc_addr: &str) -> Result<ChainClient, InvalidUri> {
+        Ok(ChainClient {
+            transport: Box::new(GrpcTransport::new(lcd_addr, grpc_addr)?),
+        })
+    }
+
+    pub async fn status(&self) -> Result<NodeInfo, CosmosError> {
+        self.transport.status().await
+    }
+
+    pub async fn get_account_data(&self, address: &str) -> Result<AccountData, CosmosError> {
+        self.transport.get_account_data(address).await
+    }
+
+    pub async fn broadcast_tx_sync(&self, tx: &Tx) -> Result<BroadcastResult, CosmosError> {
+        self.transport.broadcast_tx_sync(tx).await
+    }
+
+    pub async fn get_tx(&self, hash: &str) -> Result<Option<TxLookup>, CosmosError> {
+        self.transport.get_tx(hash).await
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRpcRequest<P> {
+    jsonrpc: &'static str,
+    id: u32,
+    method: String,
+    params: P,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse<R> {
+    result: Option<R>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct StatusResult {
+    node_info: NodeInfo,
+}
+
+#[derive(Serialize)]
+struct AbciQueryParams {
+    path: String,
+    data: String,
+    prove: bool,
+}
+
+#[derive(Deserialize)]
+struct AbciQueryResult {
+    response: AbciQueryResponse,
+}
+
+#[derive(Deserialize)]
+struct AbciQueryResponse {
+    value: String,
+}
+
+#[derive(Serialize)]
+struct BroadcastTxParams {
+    tx: String,
+}
+
+#[derive(Deserialize)]
+struct BroadcastTxResult {
+    hash: String,
+    #[serde(default)]
+    code: u32,
+    #[serde(default)]
+    log: String,
+}
+
+#[derive(Serialize)]
+struct TxParams {
+    hash: String,
+    prove: bool,
+}
+
+#[derive(Deserialize)]
+struct TxResult {
+    height: String,
+    tx_result: TxExecResult,
+}
+
+#[derive(Deserialize)]
+struct TxExecResult {
+    #[serde(default)]
+    code: u32,
+    #[serde(default)]
+    log: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_a_status_response() {
+        let response: JsonRpcResponse<StatusResult> = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "result": {
+                "node_info": {
+                    "id": "abc123",
+                    "listen_addr": "tcp://0.0.0.0:26656",
+                    "network": "testchain",
+                    "version": "0.34.0",
+                    "moniker": "test-node"
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!("testchain", response.result.unwrap().node_info.network);
+    }
+
+    #[test]
+    fn deserializes_a_json_rpc_error() {
+        let response: JsonRpcResponse<StatusResult> = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "error": { "code": -32603, "message": "tx not found" }
+        }))
+        .unwrap();
+
+        assert!(response.result.is_none());
+        assert_eq!("tx not found", response.error.unwrap().message);
+    }
+}
@@ -2,29 +2,126 @@
 //!
 //! This module provides a facility to build and sign transactions for cosmos based blockchains.
 
+use crate::client::CosmosClient;
 use crate::error::TxBuildError;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
-use cosmos_sdk_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
+use cosmos_sdk_proto::cosmos::crypto::multisig::v1beta1::CompactBitArray;
+use cosmos_sdk_proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::{MsgDelegate, MsgUndelegate};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::mode_info::{Multi, Single, Sum};
 use cosmos_sdk_proto::cosmos::tx::v1beta1::{
-    AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, Tx, TxBody,
+    AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, Tx, TxBody, TxRaw,
 };
 use crw_wallet::crypto::MnemonicWallet;
 use prost::EncodeError;
 use prost_types::Any;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::BTreeMap;
 
 /// Private structure used to represents the information of the account
 /// that is performing the transaction.
+#[derive(Clone)]
 struct AccountInfo {
     pub sequence: u64,
     pub number: u64,
 }
 
+/// One signer of a transaction assembled by [TxBuilder::sign_multi]: the wallet that produces
+/// their signature, and the account number/sequence their `SignerInfo` carries.
+pub struct TxSigner<'a> {
+    pub wallet: &'a MnemonicWallet,
+    pub number: u64,
+    pub sequence: u64,
+}
+
+/// A Cosmos SDK `Msg` that knows its own protobuf `Any.type_url`, so [TxBuilder::add_typed_message]
+/// doesn't force the caller to hand-copy it from the chain's registered `Msg` service - get that
+/// copy wrong (as this module's own doctest used to) and the chain silently rejects the broadcast
+/// as an unrecognized message type.
+pub trait CosmosMsg: prost::Message + Sized {
+    /// The fully qualified protobuf message type, e.g. `/cosmos.bank.v1beta1.MsgSend`.
+    const TYPE_URL: &'static str;
+
+    /// Encodes this message into a [prost_types::Any] tagged with [CosmosMsg::TYPE_URL].
+    fn into_any(self) -> Any {
+        Any {
+            type_url: Self::TYPE_URL.to_string(),
+            value: self.encode_to_vec(),
+        }
+    }
+}
+
+impl CosmosMsg for MsgSend {
+    const TYPE_URL: &'static str = "/cosmos.bank.v1beta1.MsgSend";
+}
+
+impl CosmosMsg for MsgDelegate {
+    const TYPE_URL: &'static str = "/cosmos.staking.v1beta1.MsgDelegate";
+}
+
+impl CosmosMsg for MsgUndelegate {
+    const TYPE_URL: &'static str = "/cosmos.staking.v1beta1.MsgUndelegate";
+}
+
+impl CosmosMsg for MsgWithdrawDelegatorReward {
+    const TYPE_URL: &'static str = "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward";
+}
+
+/// The gas limit [TxBuilder::sign_with_estimated_fee] signs its placeholder, simulation-only tx
+/// with. The Cosmos SDK's simulate mode only needs a syntactically valid fee, so this is never
+/// charged and never shows up in the final, re-signed transaction.
+const PLACEHOLDER_GAS_LIMIT: u64 = 200_000;
+
+/// A message registered for `SIGN_MODE_LEGACY_AMINO_JSON` signing via [TxBuilder::add_amino_message].
+/// Amino JSON has no equivalent to protobuf's `Any.type_url` scheme, so each message carries its
+/// own Amino type tag (e.g. `"cosmos-sdk/MsgSend"`) alongside its JSON-encoded value.
+#[derive(Serialize, Clone)]
+struct AminoMsg {
+    #[serde(rename = "type")]
+    amino_type: String,
+    value: serde_json::Value,
+}
+
+/// A `Coin` as it appears in a [StdFee], with its fields declared in lexicographic order
+/// (`amount`, `denom`), unlike [Coin]'s own `denom`, `amount`.
+#[derive(Serialize)]
+struct StdCoin {
+    amount: String,
+    denom: String,
+}
+
+/// The `fee` field of a [StdSignDoc], mirroring the Cosmos SDK's `StdFee`.
+#[derive(Serialize)]
+struct StdFee {
+    amount: Vec<StdCoin>,
+    gas: String,
+}
+
+/// The canonical JSON document signed under `SIGN_MODE_LEGACY_AMINO_JSON` (the Cosmos SDK's
+/// `StdSignDoc`), produced by [TxBuilder::sign_amino]. Field names are declared here in
+/// lexicographic order, which `serde_json` preserves, so every signer serializes identical bytes
+/// for the same transaction. `msgs` keeps the order the messages were added in.
+#[derive(Serialize)]
+struct StdSignDoc {
+    account_number: String,
+    chain_id: String,
+    fee: StdFee,
+    memo: String,
+    msgs: Vec<AminoMsg>,
+    sequence: String,
+}
+
 /// The single signer transaction builder.
+#[derive(Clone)]
 pub struct TxBuilder {
     chain_id: String,
     account_info: Option<AccountInfo>,
     tx_body: TxBody,
     fee: Option<Fee>,
+    amino_messages: Vec<AminoMsg>,
 }
 
 impl TxBuilder {
@@ -59,7 +156,7 @@ impl TxBuilder {
     ///              .account_info(1, 5)
     ///              .fee("stake", "10", 300_000)
     ///              .timeout_height(1000)
-    ///              .add_message("/cosmos.bank.v1beta1.Msg/Send", msg_snd).unwrap()
+    ///              .add_typed_message(msg_snd)
     ///              .sign(&wallet).unwrap();
     ///```
     pub fn new(chain_id: &str) -> TxBuilder {
@@ -74,6 +171,7 @@ impl TxBuilder {
                 non_critical_extension_options: Vec::<Any>::new(),
             },
             fee: Option::None,
+            amino_messages: Vec::new(),
         }
     }
 
@@ -107,6 +205,35 @@ impl TxBuilder {
         self
     }
 
+    /// Appends an already wire-encoded message, for callers (e.g. a wasm-bindgen bridge) that
+    /// receive a [prost_types::Any] from their caller rather than a typed [prost::Message] this
+    /// crate can encode itself.
+    pub fn add_any_message(self, any: Any) -> Self {
+        self.add_message_raw(&any.type_url, any.value)
+    }
+
+    /// Appends `msg` to the transaction, deriving its `Any.type_url` from [CosmosMsg::TYPE_URL]
+    /// instead of requiring it to be passed (and possibly mistyped, see [TxBuilder::add_message])
+    /// by hand.
+    pub fn add_typed_message<M: CosmosMsg>(self, msg: M) -> Self {
+        let any = msg.into_any();
+        self.add_message_raw(&any.type_url, any.value)
+    }
+
+    /// Registers `msg` for signing under `SIGN_MODE_LEGACY_AMINO_JSON` (see [TxBuilder::sign_amino]),
+    /// tagged with its Amino type (e.g. `"cosmos-sdk/MsgSend"`). Amino JSON has no equivalent to
+    /// protobuf's `Any.type_url` scheme, so - unlike [TxBuilder::add_message] - the caller supplies
+    /// `msg` as its already Amino-JSON-encoded value rather than a [prost::Message] this crate
+    /// encodes itself; populate one amino type per message type your chain expects to sign.
+    pub fn add_amino_message(mut self, amino_type: &str, msg: serde_json::Value) -> Self {
+        self.amino_messages.push(AminoMsg {
+            amino_type: amino_type.to_owned(),
+            value: msg,
+        });
+
+        self
+    }
+
     /// Sets the transaction memo.
     pub fn memo(mut self, memo: &str) -> Self {
         self.tx_body.memo = memo.to_string();
@@ -136,6 +263,69 @@ impl TxBuilder {
         self
     }
 
+    /// Sets the transaction fee directly from an already-built [Fee], for callers (e.g. a
+    /// wasm-bindgen bridge) that receive one pre-computed from JS rather than picking a
+    /// denom/amount/gas_limit through [TxBuilder::fee].
+    pub fn fee_raw(mut self, fee: Fee) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Sets the transaction fee by pricing a known `gas_limit` with a [Decimal] `gas_price`,
+    /// rounding the resulting amount up so the transaction never under-pays (e.g. `ceil(300_000 *
+    /// 0.025) = 7500`), rather than requiring the caller to compute and format the amount `&str`
+    /// themselves as [TxBuilder::fee] does.
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if `gas_limit * gas_price` overflows a [Decimal].
+    pub fn fee_from_gas_price(
+        mut self,
+        denom: &str,
+        gas_price: Decimal,
+        gas_limit: u64,
+    ) -> Result<Self, TxBuildError> {
+        let amount = Decimal::from(gas_limit)
+            .checked_mul(gas_price)
+            .ok_or_else(|| TxBuildError::Overflow("gas_limit * gas_price overflowed".to_string()))?
+            .ceil();
+
+        self.fee = Some(Fee {
+            amount: vec![Coin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            }],
+            gas_limit,
+            payer: "".to_string(),
+            granter: "".to_string(),
+        });
+
+        Ok(self)
+    }
+
+    /// Sets the fee payer, routing the fee charge to a different account than the message
+    /// signer(s) (e.g. a service account covering gas for its users).
+    ///
+    /// Must be called after [TxBuilder::fee]/[TxBuilder::fee_raw]/[TxBuilder::fee_from_gas_price]
+    /// set a fee to carry the payer on; a no-op otherwise.
+    pub fn fee_payer(mut self, payer: &str) -> Self {
+        if let Some(fee) = self.fee.as_mut() {
+            fee.payer = payer.to_string();
+        }
+        self
+    }
+
+    /// Sets the fee granter, so the fee is paid out of a `feegrant` allowance instead of the
+    /// payer's own balance.
+    ///
+    /// Must be called after [TxBuilder::fee]/[TxBuilder::fee_raw]/[TxBuilder::fee_from_gas_price]
+    /// set a fee to carry the granter on; a no-op otherwise.
+    pub fn fee_granter(mut self, granter: &str) -> Self {
+        if let Some(fee) = self.fee.as_mut() {
+            fee.granter = granter.to_string();
+        }
+        self
+    }
+
     /// Generate the signed transaction using the provided wallet.
     ///
     /// The transaction will be signed following the `SIGN_MODE_DIRECT` specification.
@@ -213,6 +403,373 @@ impl TxBuilder {
             signatures: vec![signature],
         })
     }
+
+    /// Signs this transaction twice: once with a [PLACEHOLDER_GAS_LIMIT] placeholder fee so it
+    /// can be simulated, then again with a [Fee] sized from the real `gas_used` the simulation
+    /// reported, removing the need to hand-pick `gas_limit`/`amount` in [TxBuilder::fee] upfront.
+    ///
+    /// Mirrors the standard cosmos-sdk CLI workflow: `gas_adjustment` scales the simulated
+    /// `gas_used` to leave a safety margin (e.g. `1.3` for 30%), and `gas_price` of `fee_denom`
+    /// prices the resulting `gas_limit`; see [CosmosClient::estimate_fee] for the exact formula.
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if one of the following cases:
+    /// * If account information hasn't been set
+    /// * If an error occurs signing either the placeholder or the final transaction
+    /// * If an error occurs simulating the placeholder transaction against `client`
+    pub async fn sign_with_estimated_fee(
+        self,
+        wallet: &MnemonicWallet,
+        client: &CosmosClient,
+        fee_denom: &str,
+        gas_price: f64,
+        gas_adjustment: f64,
+    ) -> Result<Tx, TxBuildError> {
+        if self.account_info.is_none() {
+            return Result::Err(TxBuildError::NoAccountInfo);
+        }
+
+        let placeholder_tx = self
+            .clone()
+            .fee(fee_denom, "0", PLACEHOLDER_GAS_LIMIT)
+            .sign(wallet)?;
+
+        let fee = client
+            .estimate_fee(&placeholder_tx, fee_denom, gas_price, gas_adjustment)
+            .await?;
+
+        self.fee_raw(fee).sign(wallet)
+    }
+
+    /// Generate the signed transaction using the provided wallet, signing under
+    /// `SIGN_MODE_LEGACY_AMINO_JSON` instead of `SIGN_MODE_DIRECT`. Ledger and other
+    /// hardware-constrained signers can't display protobuf sign docs, so they require the
+    /// canonical `StdSignDoc` JSON document this mode signs instead; the resulting `Tx`'s
+    /// `auth_info` still carries the usual protobuf `TxBody`/`AuthInfo`, only the signature itself
+    /// is computed over the Amino JSON document.
+    ///
+    /// Messages must have been added through [TxBuilder::add_amino_message], not
+    /// [TxBuilder::add_message]/[TxBuilder::add_any_message]: Amino JSON has no equivalent to
+    /// protobuf's `Any.type_url` scheme, so there is no way to derive an Amino type tag from a
+    /// message added through those. Each protobuf message must have a matching Amino twin added
+    /// in the same order, or the bytes a hardware wallet displays and signs would silently
+    /// diverge from the `TxBody` that's actually broadcast.
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if one of the following cases:
+    /// * If an error occur during the transaction serialization to protobuf
+    /// * If `self.tx_body.messages.len() != self.amino_messages.len()`
+    /// * If an error occur serializing the Amino JSON sign doc
+    /// * If an error occur during the transaction signature.
+    pub fn sign_amino(self, wallet: &MnemonicWallet) -> Result<Tx, TxBuildError> {
+        if self.account_info.is_none() {
+            return Result::Err(TxBuildError::NoAccountInfo);
+        }
+
+        if self.fee.is_none() {
+            return Result::Err(TxBuildError::NoFee);
+        }
+
+        if self.tx_body.messages.len() != self.amino_messages.len() {
+            return Result::Err(TxBuildError::MismatchedAminoMessageCount(format!(
+                "expected {} amino message(s), got {}",
+                self.tx_body.messages.len(),
+                self.amino_messages.len()
+            )));
+        }
+
+        let account_info = self.account_info.as_ref().unwrap();
+        let fee = self.fee.as_ref().unwrap();
+
+        let std_sign_doc = StdSignDoc {
+            account_number: account_info.number.to_string(),
+            chain_id: self.chain_id.clone(),
+            fee: StdFee {
+                amount: fee
+                    .amount
+                    .iter()
+                    .map(|coin| StdCoin {
+                        amount: coin.amount.clone(),
+                        denom: coin.denom.clone(),
+                    })
+                    .collect(),
+                gas: fee.gas_limit.to_string(),
+            },
+            memo: self.tx_body.memo.clone(),
+            msgs: self.amino_messages.clone(),
+            sequence: account_info.sequence.to_string(),
+        };
+
+        // Canonical JSON: lexicographically sorted keys (the field declaration order above) and
+        // no insignificant whitespace, so every signer reproduces identical bytes.
+        let sign_doc_buffer = serde_json::to_vec(&std_sign_doc)
+            .map_err(|err| TxBuildError::Encode(err.to_string()))?;
+
+        // sign the doc buffer
+        let signature = wallet
+            .sign(&sign_doc_buffer)
+            .map_err(|err| TxBuildError::Sign(err.to_string()))?;
+
+        let mut serialized_key: Vec<u8> = Vec::new();
+        prost::Message::encode(&wallet.get_pub_key().to_bytes(), &mut serialized_key)?;
+
+        // TODO extract a better key type (not an Any type)
+        let public_key_any = Any {
+            type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+            value: serialized_key,
+        };
+
+        // SIGN_MODE_LEGACY_AMINO_JSON
+        let single_signer = Single { mode: 127 };
+        let signer_info = SignerInfo {
+            public_key: Some(public_key_any),
+            mode_info: Some(ModeInfo {
+                sum: Some(Sum::Single(single_signer)),
+            }),
+            sequence: account_info.sequence,
+        };
+
+        let auth_info = AuthInfo {
+            signer_infos: vec![signer_info],
+            fee: Some(fee.clone()),
+        };
+
+        Result::Ok(Tx {
+            body: Some(self.tx_body),
+            auth_info: Some(auth_info),
+            signatures: vec![signature],
+        })
+    }
+
+    /// Generates the transaction signed by several distinct signers at once (e.g. a fee payer and
+    /// a message signer, or any other account combination the chain's messages require), rather
+    /// than [TxBuilder::sign]'s single `SignerInfo`/signature. Every signer is signed under
+    /// `SIGN_MODE_DIRECT`.
+    ///
+    /// Unlike [TxBuilder::sign], each `SignDoc` carries its own signer's `account_number` - the
+    /// rest (`body_bytes`, `auth_info_bytes`, `chain_id`) is identical across signers, since
+    /// `auth_info_bytes` already encodes every signer's `SignerInfo`. `AuthInfo.signer_infos` and
+    /// the resulting `Tx.signatures` are both in `signers` order, as the Cosmos signing spec
+    /// requires.
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if one of the following cases:
+    /// * If `signers` is empty
+    /// * If the transaction fee hasn't been set
+    /// * If an error occur during the transaction serialization to protobuf
+    /// * If an error occur during one of the signers' transaction signature
+    pub fn sign_multi(self, signers: &[TxSigner]) -> Result<Tx, TxBuildError> {
+        if signers.is_empty() {
+            return Result::Err(TxBuildError::NoAccountInfo);
+        }
+
+        if self.fee.is_none() {
+            return Result::Err(TxBuildError::NoFee);
+        }
+
+        let mut tx_body_buffer = Vec::new();
+        prost::Message::encode(&self.tx_body, &mut tx_body_buffer)?;
+
+        let signer_infos = signers
+            .iter()
+            .map(|signer| {
+                let mut serialized_key: Vec<u8> = Vec::new();
+                prost::Message::encode(&signer.wallet.get_pub_key().to_bytes(), &mut serialized_key)?;
+
+                // TODO extract a better key type (not an Any type)
+                Ok(SignerInfo {
+                    public_key: Some(Any {
+                        type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+                        value: serialized_key,
+                    }),
+                    mode_info: Some(ModeInfo {
+                        sum: Some(Sum::Single(Single { mode: 1 })),
+                    }),
+                    sequence: signer.sequence,
+                })
+            })
+            .collect::<Result<Vec<SignerInfo>, TxBuildError>>()?;
+
+        let auth_info = AuthInfo {
+            signer_infos,
+            fee: Some(self.fee.as_ref().unwrap().clone()),
+        };
+
+        let mut auth_info_buffer = Vec::new();
+        prost::Message::encode(&auth_info, &mut auth_info_buffer)?;
+
+        let signatures = signers
+            .iter()
+            .map(|signer| {
+                let sign_doc = SignDoc {
+                    body_bytes: tx_body_buffer.clone(),
+                    auth_info_bytes: auth_info_buffer.clone(),
+                    chain_id: self.chain_id.clone(),
+                    account_number: signer.number,
+                };
+                let mut sign_doc_buffer = Vec::new();
+                prost::Message::encode(&sign_doc, &mut sign_doc_buffer)?;
+
+                signer
+                    .wallet
+                    .sign(&sign_doc_buffer)
+                    .map_err(|err| TxBuildError::Sign(err.to_string()))
+            })
+            .collect::<Result<Vec<Vec<u8>>, TxBuildError>>()?;
+
+        Result::Ok(Tx {
+            body: Some(self.tx_body),
+            auth_info: Some(auth_info),
+            signatures,
+        })
+    }
+
+    /// Converts this builder into a [MultiSigTxBuilder], to assemble a transaction signed by a
+    /// legacy-amino threshold multisig account instead of a single [MnemonicWallet].
+    ///
+    /// `member_pub_keys` must list every member's serialized `/cosmos.crypto.secp256k1.PubKey`
+    /// protobuf bytes in the exact order the multisig account was created with - that order is
+    /// baked into the account's address, and [MultiSigTxBuilder::collect_signature]'s
+    /// `signer_index` refers to positions in this list.
+    pub fn multisig(self, member_pub_keys: Vec<Vec<u8>>, threshold: u32) -> MultiSigTxBuilder {
+        MultiSigTxBuilder {
+            account_info: self.account_info,
+            tx_body: self.tx_body,
+            fee: self.fee,
+            member_pub_keys,
+            threshold,
+            signatures: BTreeMap::new(),
+        }
+    }
+}
+
+/// Assembles a legacy-amino threshold multisig transaction from member signatures collected
+/// offline (e.g. one per co-signer over a `SignDoc`/`StdSignDoc` produced by [TxBuilder::sign] or
+/// [TxBuilder::sign_amino] on each member's own wallet), rather than signing anything itself.
+///
+/// Build one via [TxBuilder::multisig], feed in each co-signer's signature as it arrives through
+/// [MultiSigTxBuilder::collect_signature], then call [MultiSigTxBuilder::finalize] once at least
+/// `threshold` of them are present.
+pub struct MultiSigTxBuilder {
+    account_info: Option<AccountInfo>,
+    tx_body: TxBody,
+    fee: Option<Fee>,
+    member_pub_keys: Vec<Vec<u8>>,
+    threshold: u32,
+    signatures: BTreeMap<usize, Vec<u8>>,
+}
+
+impl MultiSigTxBuilder {
+    /// Records `signature` as having been produced by the member at `signer_index` into
+    /// `member_pub_keys` (as passed to [TxBuilder::multisig]). Collecting a signature for an
+    /// index that already has one overwrites it.
+    pub fn collect_signature(mut self, signer_index: usize, signature: Vec<u8>) -> Self {
+        self.signatures.insert(signer_index, signature);
+        self
+    }
+
+    /// Assembles the collected signatures into a `TxRaw` carrying a single `SignerInfo` for the
+    /// multisig account: its public key is a `/cosmos.crypto.multisig.LegacyAminoPubKey` listing
+    /// every member, its `ModeInfo` is a `Sum::Multi` bitarray marking which members signed, and
+    /// its signature is the concatenation of the collected member signatures in member order.
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if one of the following cases:
+    /// * If fewer than `threshold` signatures have been collected
+    /// * If account information hasn't been set on the originating [TxBuilder]
+    /// * If transaction fee hasn't been set on the originating [TxBuilder]
+    /// * If an error occur during the transaction serialization to protobuf
+    pub fn finalize(self) -> Result<TxRaw, TxBuildError> {
+        if self.account_info.is_none() {
+            return Result::Err(TxBuildError::NoAccountInfo);
+        }
+
+        if self.fee.is_none() {
+            return Result::Err(TxBuildError::NoFee);
+        }
+
+        if self.signatures.len() < self.threshold as usize {
+            return Result::Err(TxBuildError::NotEnoughSignatures(format!(
+                "{} of {} required signatures collected",
+                self.signatures.len(),
+                self.threshold
+            )));
+        }
+
+        let account_info = self.account_info.as_ref().unwrap();
+
+        let public_keys = self
+            .member_pub_keys
+            .iter()
+            .map(|pub_key| Any {
+                type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+                value: pub_key.clone(),
+            })
+            .collect();
+
+        let mut serialized_pub_key: Vec<u8> = Vec::new();
+        prost::Message::encode(
+            &LegacyAminoPubKey {
+                threshold: self.threshold,
+                public_keys,
+            },
+            &mut serialized_pub_key,
+        )?;
+
+        let public_key_any = Any {
+            type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_string(),
+            value: serialized_pub_key,
+        };
+
+        // One bit per member, set for those who signed, packed MSB-first as the Cosmos SDK's
+        // own `CompactBitArray` does.
+        let mut elems = vec![0u8; (self.member_pub_keys.len() + 7) / 8];
+        for &signer_index in self.signatures.keys() {
+            elems[signer_index / 8] |= 0x80 >> (signer_index % 8);
+        }
+
+        let mode_infos = self
+            .signatures
+            .keys()
+            .map(|_| ModeInfo {
+                sum: Some(Sum::Single(Single { mode: 1 })),
+            })
+            .collect();
+
+        let signer_info = SignerInfo {
+            public_key: Some(public_key_any),
+            mode_info: Some(ModeInfo {
+                sum: Some(Sum::Multi(Multi {
+                    bitarray: Some(CompactBitArray {
+                        extra_bits_stored: (self.member_pub_keys.len() % 8) as u32,
+                        elems,
+                    }),
+                    mode_infos,
+                })),
+            }),
+            sequence: account_info.sequence,
+        };
+
+        let auth_info = AuthInfo {
+            signer_infos: vec![signer_info],
+            fee: Some(self.fee.unwrap()),
+        };
+
+        let mut body_bytes: Vec<u8> = Vec::new();
+        prost::Message::encode(&self.tx_body, &mut body_bytes)?;
+
+        let mut auth_info_bytes: Vec<u8> = Vec::new();
+        prost::Message::encode(&auth_info, &mut auth_info_bytes)?;
+
+        // Concatenated in member order, matching the order `mode_infos`/`bitarray` describe them.
+        let signature = self.signatures.into_values().flatten().collect();
+
+        Result::Ok(TxRaw {
+            body_bytes,
+            auth_info_bytes,
+            signatures: vec![signature],
+        })
+    }
 }
 
 impl From<EncodeError> for TxBuildError {
@@ -221,11 +778,58 @@ impl From<EncodeError> for TxBuildError {
     }
 }
 
+/// Verifies a signed transaction previously produced by [TxBuilder::sign] and encoded into raw
+/// `TxRaw` bytes (e.g. by [crate::client::encode_tx_raw]): reconstructs the `SignDoc` the
+/// transaction was signed over from the decoded `TxRaw`, then checks the embedded signature
+/// against the embedded `SignerInfo.public_key`. Lets a caller validate a broadcast-ready tx
+/// before sending it.
+///
+/// # Errors
+/// Returns an [`Err`] if one of the following cases:
+/// * If `tx_bytes` isn't a validly encoded `TxRaw`, or its `auth_info_bytes` isn't a validly
+///   encoded `AuthInfo`.
+/// * If the `TxRaw` carries no signer info, no public key, or no signature.
+pub fn verify_tx_raw(
+    tx_bytes: &[u8],
+    chain_id: &str,
+    account_number: u64,
+) -> Result<bool, TxBuildError> {
+    let tx_raw: TxRaw = prost::Message::decode(tx_bytes)?;
+    let auth_info: AuthInfo = prost::Message::decode(tx_raw.auth_info_bytes.as_slice())?;
+
+    let public_key_any = auth_info
+        .signer_infos
+        .first()
+        .and_then(|signer_info| signer_info.public_key.as_ref())
+        .ok_or_else(|| TxBuildError::Verify("tx has no signer public key".to_string()))?;
+    let pub_key: Vec<u8> = prost::Message::decode(public_key_any.value.as_slice())?;
+
+    let signature = tx_raw
+        .signatures
+        .first()
+        .ok_or_else(|| TxBuildError::Verify("tx has no signature".to_string()))?;
+
+    let sign_doc = SignDoc {
+        body_bytes: tx_raw.body_bytes,
+        auth_info_bytes: tx_raw.auth_info_bytes,
+        chain_id: chain_id.to_owned(),
+        account_number,
+    };
+    let mut sign_doc_buffer = Vec::new();
+    prost::Message::encode(&sign_doc, &mut sign_doc_buffer)?;
+
+    MnemonicWallet::verify(&sign_doc_buffer, signature, &pub_key)
+        .map_err(|err| TxBuildError::Verify(err.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::tx::{TxBuildError, TxBuilder};
+    use crate::tx::{CosmosMsg, MultiSigTxBuilder, TxBuildError, TxBuilder, TxSigner};
     use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
     use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, TxBody, TxRaw};
+    use rust_decimal::Decimal;
     use crw_wallet::crypto::MnemonicWallet;
 
     static TEST_MNEMONIC: &str = "elephant luggage finger obscure nest smooth flag clay recycle unfair capital category organ bicycle gallery sight canyon hotel dutch skull today pink scale aisle";
@@ -251,7 +855,7 @@ mod tests {
             .timeout_height(0);
 
         let sign_result = tx_builder
-            .add_message("/cosmos.bank.v1beta1.Msg/Send", msg_snd)
+            .add_message("/cosmos.bank.v1beta1.MsgSend", msg_snd)
             .unwrap()
             .sign(&wallet);
 
@@ -277,7 +881,7 @@ mod tests {
             .memo("Test memo")
             .fee("stake", "10", 300_000)
             .timeout_height(0)
-            .add_message("/cosmos.bank.v1beta1.Msg/Send", msg_snd)
+            .add_message("/cosmos.bank.v1beta1.MsgSend", msg_snd)
             .unwrap()
             .sign(&wallet);
 
@@ -304,7 +908,7 @@ mod tests {
             .account_info(1, 5)
             .fee("stake", "10", 300_000)
             .timeout_height(1000)
-            .add_message("/cosmos.bank.v1beta1.Msg/Send", msg_snd)
+            .add_message("/cosmos.bank.v1beta1.MsgSend", msg_snd)
             .unwrap()
             .sign(&wallet)
             .unwrap();
@@ -321,4 +925,345 @@ mod tests {
         // Check that the sequence is the same passed to account_info
         assert_eq!(1, auth_info.signer_infos[0].sequence);
     }
+
+    #[test]
+    fn test_add_typed_message_sets_correct_type_url() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let amount = Coin {
+            denom: "stake".to_string(),
+            amount: "10".to_string(),
+        };
+        let msg_snd = MsgSend {
+            from_address: wallet.get_bech32_address("desmos").unwrap(),
+            to_address: "desmos18ek6mnlxj8sysrtvu60k5zj0re7s5n42yncner".to_string(),
+            amount: vec![amount],
+        };
+
+        let tx = TxBuilder::new("testchain")
+            .account_info(1, 5)
+            .fee("stake", "10", 300_000)
+            .add_typed_message(msg_snd)
+            .sign(&wallet)
+            .unwrap();
+
+        assert_eq!(MsgSend::TYPE_URL, tx.body.unwrap().messages[0].type_url);
+    }
+
+    #[test]
+    fn test_fee_from_gas_price_rounds_up() {
+        let fee = TxBuilder::new("testchain")
+            .fee_from_gas_price("stake", Decimal::new(25, 3), 300_000)
+            .unwrap()
+            .fee_payer("desmos1payer")
+            .fee_granter("desmos1granter")
+            .fee
+            .unwrap();
+
+        // 300_000 * 0.025 = 7500, exact, so rounding up leaves it unchanged.
+        assert_eq!("7500", fee.amount[0].amount);
+        assert_eq!("stake", fee.amount[0].denom);
+        assert_eq!(300_000, fee.gas_limit);
+        assert_eq!("desmos1payer", fee.payer);
+        assert_eq!("desmos1granter", fee.granter);
+    }
+
+    #[test]
+    fn test_fee_from_gas_price_overflow() {
+        let result = TxBuilder::new("testchain").fee_from_gas_price("stake", Decimal::MAX, 2);
+
+        assert!(result.is_err());
+        assert!(matches!(result.err().unwrap(), TxBuildError::Overflow(_)));
+    }
+
+    #[test]
+    fn test_verify_tx_raw() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let amount = Coin {
+            denom: "stake".to_string(),
+            amount: "10".to_string(),
+        };
+        let msg_snd = MsgSend {
+            from_address: wallet.get_bech32_address("desmos").unwrap(),
+            to_address: "desmos18ek6mnlxj8sysrtvu60k5zj0re7s5n42yncner".to_string(),
+            amount: vec![amount],
+        };
+
+        let tx = TxBuilder::new("testchain")
+            .memo("Test memo")
+            .account_info(1, 5)
+            .fee("stake", "10", 300_000)
+            .timeout_height(1000)
+            .add_message("/cosmos.bank.v1beta1.MsgSend", msg_snd)
+            .unwrap()
+            .sign(&wallet)
+            .unwrap();
+
+        let tx_bytes = crate::client::encode_tx_raw(&tx).unwrap();
+
+        assert!(super::verify_tx_raw(&tx_bytes, "testchain", 5).unwrap());
+        // A mismatched chain id changes the sign doc, so the embedded signature no longer matches.
+        assert!(!super::verify_tx_raw(&tx_bytes, "otherchain", 5).unwrap());
+    }
+
+    #[test]
+    fn test_sign_amino() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let msg_snd = MsgSend {
+            from_address: wallet.get_bech32_address("desmos").unwrap(),
+            to_address: "desmos18ek6mnlxj8sysrtvu60k5zj0re7s5n42yncner".to_string(),
+            amount: vec![Coin { denom: "stake".to_string(), amount: "10".to_string() }],
+        };
+        let amino_msg_snd = serde_json::json!({
+            "from_address": msg_snd.from_address,
+            "to_address": msg_snd.to_address,
+            "amount": [{"denom": "stake", "amount": "10"}],
+        });
+
+        let tx = TxBuilder::new("testchain")
+            .memo("Test memo")
+            .account_info(1, 5)
+            .fee("stake", "10", 300_000)
+            .timeout_height(1000)
+            .add_message("/cosmos.bank.v1beta1.Msg/Send", msg_snd)
+            .unwrap()
+            .add_amino_message("cosmos-sdk/MsgSend", amino_msg_snd)
+            .sign_amino(&wallet)
+            .unwrap();
+
+        let auth_info = tx.auth_info.unwrap();
+
+        // Should be 1 since sign_amino signs with a single wallet.
+        assert_eq!(1, tx.signatures.len());
+        assert_eq!(1, auth_info.signer_infos.len());
+        assert_eq!(1, auth_info.signer_infos[0].sequence);
+
+        // SIGN_MODE_LEGACY_AMINO_JSON
+        let mode_info = auth_info.signer_infos[0].mode_info.as_ref().unwrap();
+        assert_eq!(Some(Sum::Single(Single { mode: 127 })), mode_info.sum);
+    }
+
+    #[test]
+    fn test_sign_amino_missing_fee() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let amino_msg_snd = serde_json::json!({
+            "from_address": wallet.get_bech32_address("desmos").unwrap(),
+            "to_address": "desmos18ek6mnlxj8sysrtvu60k5zj0re7s5n42yncner",
+            "amount": [{"denom": "stake", "amount": "10"}],
+        });
+
+        let sign_result = TxBuilder::new("testchain")
+            .memo("Test memo")
+            .account_info(0, 0)
+            .timeout_height(0)
+            .add_amino_message("cosmos-sdk/MsgSend", amino_msg_snd)
+            .sign_amino(&wallet);
+
+        assert!(sign_result.is_err());
+        assert_eq!(TxBuildError::NoFee, sign_result.err().unwrap());
+    }
+
+    #[test]
+    fn test_sign_amino_rejects_a_mismatched_message_count() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let msg_snd = MsgSend {
+            from_address: wallet.get_bech32_address("desmos").unwrap(),
+            to_address: "desmos18ek6mnlxj8sysrtvu60k5zj0re7s5n42yncner".to_string(),
+            amount: vec![Coin { denom: "stake".to_string(), amount: "10".to_string() }],
+        };
+
+        // A protobuf message was registered, but no Amino twin for it: without the check, the
+        // Amino sign doc a hardware wallet displays/signs would silently cover zero messages
+        // while the broadcast `TxBody` carries one.
+        let sign_result = TxBuilder::new("testchain")
+            .memo("Test memo")
+            .account_info(1, 5)
+            .fee("stake", "10", 300_000)
+            .timeout_height(1000)
+            .add_message("/cosmos.bank.v1beta1.Msg/Send", msg_snd)
+            .unwrap()
+            .sign_amino(&wallet);
+
+        assert!(matches!(
+            sign_result,
+            Err(TxBuildError::MismatchedAminoMessageCount(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_amino_broadcasts_like_sign() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let amount = Coin {
+            denom: "stake".to_string(),
+            amount: "10".to_string(),
+        };
+        let msg_snd = MsgSend {
+            from_address: wallet.get_bech32_address("desmos").unwrap(),
+            to_address: "desmos18ek6mnlxj8sysrtvu60k5zj0re7s5n42yncner".to_string(),
+            amount: vec![amount],
+        };
+        let amino_msg_snd = serde_json::json!({
+            "from_address": msg_snd.from_address,
+            "to_address": msg_snd.to_address,
+            "amount": [{"denom": "stake", "amount": "10"}],
+        });
+
+        // Registering both the protobuf message (so the chain has something to execute) and its
+        // Amino JSON twin (so the Ledger-displayed sign bytes match it) is the documented usage.
+        let tx = TxBuilder::new("testchain")
+            .memo("Test memo")
+            .account_info(1, 5)
+            .fee("stake", "10", 300_000)
+            .timeout_height(1000)
+            .add_message("/cosmos.bank.v1beta1.MsgSend", msg_snd)
+            .unwrap()
+            .add_amino_message("cosmos-sdk/MsgSend", amino_msg_snd)
+            .sign_amino(&wallet)
+            .unwrap();
+
+        // No special-casing needed: an Amino-signed `Tx` carries the same protobuf `TxBody`/
+        // `AuthInfo` shape as a `SIGN_MODE_DIRECT` one, so it encodes for `CosmosClient::broadcast_tx`
+        // the exact same way.
+        let tx_raw_bytes = crate::client::encode_tx_raw(&tx).unwrap();
+        let tx_raw: TxRaw = prost::Message::decode(tx_raw_bytes.as_slice()).unwrap();
+
+        assert_eq!(1, tx_raw.signatures.len());
+
+        let auth_info: AuthInfo =
+            prost::Message::decode(tx_raw.auth_info_bytes.as_slice()).unwrap();
+        let mode_info = auth_info.signer_infos[0].mode_info.as_ref().unwrap();
+        assert_eq!(Some(Sum::Single(Single { mode: 127 })), mode_info.sum);
+
+        let tx_body: TxBody = prost::Message::decode(tx_raw.body_bytes.as_slice()).unwrap();
+        assert_eq!(1, tx_body.messages.len());
+    }
+
+    #[test]
+    fn test_sign_multi() {
+        let signer_0 = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let signer_1 = MnemonicWallet::new(TEST_MNEMONIC, "m/44'/852'/0'/0/1").unwrap();
+
+        let amount = Coin {
+            denom: "stake".to_string(),
+            amount: "10".to_string(),
+        };
+        let msg_snd = MsgSend {
+            from_address: signer_0.get_bech32_address("desmos").unwrap(),
+            to_address: signer_1.get_bech32_address("desmos").unwrap(),
+            amount: vec![amount],
+        };
+
+        let tx = TxBuilder::new("testchain")
+            .memo("Test memo")
+            .fee("stake", "10", 300_000)
+            .add_typed_message(msg_snd)
+            .sign_multi(&[
+                TxSigner {
+                    wallet: &signer_0,
+                    number: 1,
+                    sequence: 5,
+                },
+                TxSigner {
+                    wallet: &signer_1,
+                    number: 2,
+                    sequence: 0,
+                },
+            ])
+            .unwrap();
+
+        let auth_info = tx.auth_info.unwrap();
+
+        // One `SignerInfo`/signature per signer, in signer order.
+        assert_eq!(2, auth_info.signer_infos.len());
+        assert_eq!(2, tx.signatures.len());
+        assert_eq!(5, auth_info.signer_infos[0].sequence);
+        assert_eq!(0, auth_info.signer_infos[1].sequence);
+        assert_ne!(tx.signatures[0], tx.signatures[1]);
+    }
+
+    #[test]
+    fn test_sign_multi_requires_at_least_one_signer() {
+        let sign_result = TxBuilder::new("testchain")
+            .fee("stake", "10", 300_000)
+            .sign_multi(&[]);
+
+        assert!(sign_result.is_err());
+        assert_eq!(TxBuildError::NoAccountInfo, sign_result.err().unwrap());
+    }
+
+    /// Builds the 2-member multisig transaction both multisig tests start from, signing the same
+    /// `SignDoc` independently with each member's own wallet.
+    fn build_multisig_tx_builder() -> (MultiSigTxBuilder, Vec<u8>, Vec<u8>) {
+        let member_0 = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let member_1 = MnemonicWallet::new(TEST_MNEMONIC, "m/44'/852'/0'/0/1").unwrap();
+
+        let amount = Coin {
+            denom: "stake".to_string(),
+            amount: "10".to_string(),
+        };
+        let msg_snd = MsgSend {
+            from_address: "desmos1qcdd3mtfnrpgf5aykkxjx87aauyel5uuy2z49f".to_string(),
+            to_address: "desmos18ek6mnlxj8sysrtvu60k5zj0re7s5n42yncner".to_string(),
+            amount: vec![amount],
+        };
+
+        let make_builder = || {
+            TxBuilder::new("testchain")
+                .memo("Test memo")
+                .account_info(1, 5)
+                .fee("stake", "10", 300_000)
+                .timeout_height(1000)
+                .add_message("/cosmos.bank.v1beta1.MsgSend", msg_snd.clone())
+                .unwrap()
+        };
+
+        let signature_0 = make_builder().sign(&member_0).unwrap().signatures[0].clone();
+        let signature_1 = make_builder().sign(&member_1).unwrap().signatures[0].clone();
+
+        let member_pub_keys = vec![
+            prost::Message::encode_to_vec(&member_0.get_pub_key().to_bytes()),
+            prost::Message::encode_to_vec(&member_1.get_pub_key().to_bytes()),
+        ];
+
+        let multisig_builder = make_builder().multisig(member_pub_keys, 2);
+
+        (multisig_builder, signature_0, signature_1)
+    }
+
+    #[test]
+    fn test_multisig_not_enough_signatures() {
+        let (multisig_builder, signature_0, _) = build_multisig_tx_builder();
+
+        let finalize_result = multisig_builder.collect_signature(0, signature_0).finalize();
+
+        assert!(finalize_result.is_err());
+        assert!(matches!(
+            finalize_result.err().unwrap(),
+            TxBuildError::NotEnoughSignatures(_)
+        ));
+    }
+
+    #[test]
+    fn test_multisig_finalize() {
+        let (multisig_builder, signature_0, signature_1) = build_multisig_tx_builder();
+
+        let tx_raw = multisig_builder
+            .collect_signature(0, signature_0.clone())
+            .collect_signature(1, signature_1.clone())
+            .finalize()
+            .unwrap();
+
+        assert_eq!(1, tx_raw.signatures.len());
+        // Concatenated in member order.
+        let expected_signature: Vec<u8> = signature_0
+            .into_iter()
+            .chain(signature_1.into_iter())
+            .collect();
+        assert_eq!(expected_signature, tx_raw.signatures[0]);
+    }
 }
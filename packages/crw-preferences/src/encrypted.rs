@@ -1,17 +1,47 @@
 //! Module that provides an implementation of [Preferences] that saves the values encrypted into
-//! the device storage.  
-//! The data are securely stored into the device storage using the Chacha20Poly1305 algorithm.
+//! the device storage.
+//! The data are securely stored into the device storage using the Chacha20Poly1305 algorithm,
+//! with the symmetric key stretched from the user's password via Argon2id.
 
 use crate::io;
 use crate::io::IoError;
-use crate::preferences::{Preferences, PreferencesError, Result};
+use crate::preferences::{DefaultValue, Preferences, PreferencesError, Result, ValueType};
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::DecodeError;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use cocoon::{Cocoon, Error as CocoonErr};
+use hmac::Hmac;
+use pbkdf2::pbkdf2;
+use rand::rngs::OsRng;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::Sha512;
 use std::collections::HashMap;
 use std::result::Result as StdResult;
 use thiserror::Error;
 
+/// Magic bytes marking a store encrypted with one of the versioned headers described below.
+/// Stores written before this header existed don't carry it, which is how [load_from_disk]
+/// tells the formats apart and falls back to the legacy, [Cocoon]-only derivation.
+const HEADER_MAGIC: &[u8; 4] = b"EPK1";
+/// Version of the `HEADER_MAGIC || HEADER_VERSION || salt || iterations` header that derives the
+/// key with PBKDF2-HMAC-SHA512. Kept only so stores written by older builds still open.
+const HEADER_VERSION_PBKDF2: u8 = 1;
+/// Version of the `HEADER_MAGIC || HEADER_VERSION || salt || m_cost || t_cost || p_cost` header
+/// that derives the key with Argon2id. This is the format [EncryptedPreferences::save] writes.
+const HEADER_VERSION_ARGON2: u8 = 2;
+/// Length in bytes of the random salt used to derive the encryption key.
+const SALT_LEN: usize = 16;
+/// Length in bytes of the random nonce used by ChaCha20-Poly1305.
+const NONCE_LEN: usize = 12;
+/// Argon2id memory cost, in KiB, used to stretch the password on every [EncryptedPreferences::save].
+const ARGON2_M_COST: u32 = 19456;
+/// Argon2id number of passes used to stretch the password on every [EncryptedPreferences::save].
+const ARGON2_T_COST: u32 = 2;
+/// Argon2id degree of parallelism used to stretch the password on every [EncryptedPreferences::save].
+const ARGON2_P_COST: u32 = 1;
+
 #[derive(Error, Debug)]
 pub enum EncryptedPreferencesError {
     #[error("error while decrypting the data")]
@@ -29,23 +59,63 @@ enum Value {
     Bin(Vec<u8>),
 }
 
+/// Derives a 256-bit symmetric key from `password` and `salt` using PBKDF2-HMAC-SHA512. Only
+/// used to open stores written by older builds: [EncryptedPreferences::save] always writes the
+/// Argon2id format now.
+fn derive_key_pbkdf2(password: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::<Hmac<Sha512>>(password.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+/// Derives a 256-bit symmetric key from `password` and `salt` using Argon2id, making brute-forcing
+/// a weak password memory-hard instead of mapping it directly onto the encryption key.
+fn derive_key_argon2(
+    password: &str,
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> StdResult<[u8; 32], EncryptedPreferencesError> {
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|_| EncryptedPreferencesError::DecryptionFailed)?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|_| EncryptedPreferencesError::DecryptionFailed)?;
+
+    Ok(key)
+}
+
+/// The data loaded from disk. Decoding it never needs to know which KDF produced the key that
+/// decrypted it: [EncryptedPreferences::save] always re-encrypts with the current Argon2id
+/// parameters, regardless of which format the store was loaded from.
+struct LoadedStore {
+    data: HashMap<String, Value>,
+}
+
 pub struct EncryptedPreferences {
     name: String,
     password: String,
     data: HashMap<String, Value>,
+    defaults: HashMap<String, DefaultValue>,
 }
 
 impl EncryptedPreferences {
     fn load_from_disk(
         password: &str,
         name: &str,
-    ) -> StdResult<HashMap<String, Value>, EncryptedPreferencesError> {
-        let read_result = io::load(name);
+    ) -> StdResult<LoadedStore, EncryptedPreferencesError> {
+        let read_result = io::load(name, io::PreferenceKind::Config);
 
         if read_result.is_err() {
             let err = read_result.err().unwrap();
             return match err {
-                IoError::EmptyData => Ok(HashMap::new()),
+                IoError::EmptyData => Ok(LoadedStore {
+                    data: HashMap::new(),
+                }),
                 IoError::InvalidName(s) => Err(EncryptedPreferencesError::from(
                     PreferencesError::InvalidName(s),
                 )),
@@ -55,19 +125,93 @@ impl EncryptedPreferences {
         let base64_data = read_result.unwrap();
 
         // Get the data as binary
-        let encrypted = base64::decode(base64_data)
+        let content = base64::decode(base64_data)
             .map_err(|_| EncryptedPreferencesError::from(PreferencesError::DeserializationError))?;
 
-        // Decrypt the binary data
+        if content.len() >= HEADER_MAGIC.len() + 1 && &content[..HEADER_MAGIC.len()] == HEADER_MAGIC
+        {
+            let mut offset = HEADER_MAGIC.len();
+            let version = content[offset];
+            offset += 1;
+
+            let key = match version {
+                HEADER_VERSION_ARGON2 => {
+                    if content.len() < offset + SALT_LEN + 4 + 4 + 4 {
+                        return Err(EncryptedPreferencesError::from(
+                            PreferencesError::DeserializationError,
+                        ));
+                    }
+                    let salt = &content[offset..offset + SALT_LEN];
+                    offset += SALT_LEN;
+                    let m_cost = u32::from_le_bytes(content[offset..offset + 4].try_into().unwrap());
+                    offset += 4;
+                    let t_cost = u32::from_le_bytes(content[offset..offset + 4].try_into().unwrap());
+                    offset += 4;
+                    let p_cost = u32::from_le_bytes(content[offset..offset + 4].try_into().unwrap());
+                    offset += 4;
+
+                    if content.len() < offset + NONCE_LEN {
+                        return Err(EncryptedPreferencesError::from(
+                            PreferencesError::DeserializationError,
+                        ));
+                    }
+
+                    derive_key_argon2(password, salt, m_cost, t_cost, p_cost)?
+                }
+                HEADER_VERSION_PBKDF2 => {
+                    if content.len() < offset + SALT_LEN + 4 {
+                        return Err(EncryptedPreferencesError::from(
+                            PreferencesError::DeserializationError,
+                        ));
+                    }
+                    let salt = &content[offset..offset + SALT_LEN];
+                    offset += SALT_LEN;
+                    let iterations =
+                        u32::from_le_bytes(content[offset..offset + 4].try_into().unwrap());
+                    offset += 4;
+
+                    if content.len() < offset + NONCE_LEN {
+                        return Err(EncryptedPreferencesError::from(
+                            PreferencesError::DeserializationError,
+                        ));
+                    }
+
+                    derive_key_pbkdf2(password, salt, iterations)
+                }
+                _ => {
+                    return Err(EncryptedPreferencesError::from(
+                        PreferencesError::DeserializationError,
+                    ))
+                }
+            };
+
+            let nonce = &content[offset..offset + NONCE_LEN];
+            offset += NONCE_LEN;
+            let ciphertext = &content[offset..];
+
+            let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+            let decrypted = cipher
+                .decrypt(Nonce::from_slice(nonce), ciphertext)
+                .map_err(|_| EncryptedPreferencesError::DecryptionFailed)?;
+
+            let data = bincode::deserialize::<HashMap<String, Value>>(&decrypted).map_err(|_| {
+                EncryptedPreferencesError::from(PreferencesError::DeserializationError)
+            })?;
+
+            return Ok(LoadedStore { data });
+        }
+
+        // No recognized header: fall back to the legacy, password-only Cocoon derivation.
         let cocoon = Cocoon::new(password.as_bytes());
-        let decrypted = cocoon.unwrap(&encrypted).map_err(|e| match e {
+        let decrypted = cocoon.unwrap(&content).map_err(|e| match e {
             CocoonErr::Cryptography => EncryptedPreferencesError::DecryptionFailed,
             _ => EncryptedPreferencesError::from(PreferencesError::DeserializationError),
         })?;
 
-        // Deserialize the values
-        bincode::deserialize::<HashMap<String, Value>>(&decrypted)
-            .map_err(|_| EncryptedPreferencesError::from(PreferencesError::DeserializationError))
+        let data = bincode::deserialize::<HashMap<String, Value>>(&decrypted)
+            .map_err(|_| EncryptedPreferencesError::from(PreferencesError::DeserializationError))?;
+
+        Ok(LoadedStore { data })
     }
 
     /// If already exist a preference with the provided name will be loaded otherwise will be
@@ -87,20 +231,29 @@ impl EncryptedPreferences {
         password: &str,
         name: &str,
     ) -> StdResult<EncryptedPreferences, EncryptedPreferencesError> {
+        let loaded = EncryptedPreferences::load_from_disk(password, name)?;
+
         Ok(EncryptedPreferences {
             name: name.to_owned(),
             password: password.to_owned(),
-            data: EncryptedPreferences::load_from_disk(password, name)?,
+            data: loaded.data,
+            defaults: HashMap::new(),
         })
     }
 }
 
 impl Preferences for EncryptedPreferences {
     fn get_i32(&self, key: &str) -> Option<i32> {
-        self.data.get(key).and_then(|v| match v {
-            Value::I32(i32) => Some(i32.to_owned()),
-            _ => None,
-        })
+        self.data
+            .get(key)
+            .and_then(|v| match v {
+                Value::I32(i32) => Some(i32.to_owned()),
+                _ => None,
+            })
+            .or_else(|| match self.defaults.get(key) {
+                Some(DefaultValue::I32(v)) => Some(*v),
+                _ => None,
+            })
     }
 
     fn put_i32(&mut self, key: &str, value: i32) -> Result<()> {
@@ -109,10 +262,16 @@ impl Preferences for EncryptedPreferences {
     }
 
     fn get_str(&self, key: &str) -> Option<String> {
-        self.data.get(key).and_then(|v| match v {
-            Value::String(string) => Some(string.to_owned()),
-            _ => None,
-        })
+        self.data
+            .get(key)
+            .and_then(|v| match v {
+                Value::String(string) => Some(string.to_owned()),
+                _ => None,
+            })
+            .or_else(|| match self.defaults.get(key) {
+                Some(DefaultValue::Str(v)) => Some(v.clone()),
+                _ => None,
+            })
     }
 
     fn put_str(&mut self, key: &str, value: String) -> Result<()> {
@@ -121,10 +280,16 @@ impl Preferences for EncryptedPreferences {
     }
 
     fn get_bool(&self, key: &str) -> Option<bool> {
-        self.data.get(key).and_then(|v| match v {
-            Value::Bool(bool) => Some(bool.to_owned()),
-            _ => None,
-        })
+        self.data
+            .get(key)
+            .and_then(|v| match v {
+                Value::Bool(bool) => Some(bool.to_owned()),
+                _ => None,
+            })
+            .or_else(|| match self.defaults.get(key) {
+                Some(DefaultValue::Bool(v)) => Some(*v),
+                _ => None,
+            })
     }
 
     fn put_bool(&mut self, key: &str, value: bool) -> Result<()> {
@@ -132,40 +297,120 @@ impl Preferences for EncryptedPreferences {
         Ok(())
     }
 
-    fn get_bytes(&self, key: &str) -> Option<Vec<u8>> {
-        self.data.get(key).and_then(|v| match v {
-            Value::Bin(bin) => Some(bin.to_owned()),
-            _ => None,
-        })
+    fn get_binary(&self, key: &str) -> Option<Vec<u8>> {
+        self.data
+            .get(key)
+            .and_then(|v| match v {
+                Value::Bin(bin) => Some(bin.to_owned()),
+                _ => None,
+            })
+            .or_else(|| match self.defaults.get(key) {
+                Some(DefaultValue::Binary(v)) => Some(v.clone()),
+                _ => None,
+            })
     }
 
-    fn put_bytes(&mut self, key: &str, value: Vec<u8>) -> Result<()> {
+    fn put_binary(&mut self, key: &str, value: Vec<u8>) -> Result<()> {
         self.data.insert(key.to_owned(), Value::Bin(value));
         Ok(())
     }
 
+    fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.data.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn value_type(&self, key: &str) -> ValueType {
+        match self.data.get(key) {
+            None => ValueType::Absent,
+            Some(Value::I32(_)) => ValueType::I32,
+            Some(Value::String(_)) => ValueType::String,
+            Some(Value::Bool(_)) => ValueType::Bool,
+            Some(Value::Bin(_)) => ValueType::Binary,
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.data.remove(key);
+    }
+
     fn clear(&mut self) {
         self.data.clear()
     }
 
     fn erase(&mut self) {
         self.clear();
-        io::erase(&self.name);
+        io::erase(&self.name, io::PreferenceKind::Config);
     }
 
     fn save(&self) -> Result<()> {
         let serialized =
             bincode::serialize(&self.data).map_err(|_| PreferencesError::SerializationError)?;
 
-        let storage = Cocoon::new(self.password.as_ref());
-        let encrypted = storage
-            .wrap(&serialized)
-            .map(base64::encode)
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce_bytes);
+
+        let key = derive_key_argon2(
+            &self.password,
+            &salt,
+            ARGON2_M_COST,
+            ARGON2_T_COST,
+            ARGON2_P_COST,
+        )
+        .map_err(|_| PreferencesError::SerializationError)?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), serialized.as_slice())
             .map_err(|_| PreferencesError::SerializationError)?;
 
-        io::save(&self.name, &encrypted)?;
+        let mut out = Vec::with_capacity(
+            HEADER_MAGIC.len() + 1 + SALT_LEN + 4 + 4 + 4 + NONCE_LEN + ciphertext.len(),
+        );
+        out.extend_from_slice(HEADER_MAGIC);
+        out.push(HEADER_VERSION_ARGON2);
+        out.extend_from_slice(&salt);
+        out.extend_from_slice(&ARGON2_M_COST.to_le_bytes());
+        out.extend_from_slice(&ARGON2_T_COST.to_le_bytes());
+        out.extend_from_slice(&ARGON2_P_COST.to_le_bytes());
+        out.extend_from_slice(&nonce_bytes);
+        out.extend_from_slice(&ciphertext);
+
+        let encrypted = base64::encode(out);
+
+        io::save(&self.name, &encrypted, io::PreferenceKind::Config)?;
         Ok(())
     }
+
+    /// Re-reads and authenticates the store with `old_password` before adopting `new_password`;
+    /// the in-memory data (including any change not yet saved) is then immediately re-encrypted
+    /// with a fresh salt and persisted.
+    fn change_password(&mut self, old_password: &str, new_password: &str) -> Result<()> {
+        EncryptedPreferences::load_from_disk(old_password, &self.name)?;
+
+        self.password = new_password.to_owned();
+        self.save()
+    }
+
+    fn set_default_i32(&mut self, key: &str, value: i32) {
+        self.defaults.insert(key.to_owned(), DefaultValue::I32(value));
+    }
+
+    fn set_default_str(&mut self, key: &str, value: String) {
+        self.defaults.insert(key.to_owned(), DefaultValue::Str(value));
+    }
+
+    fn set_default_bool(&mut self, key: &str, value: bool) {
+        self.defaults
+            .insert(key.to_owned(), DefaultValue::Bool(value));
+    }
+
+    fn set_default_binary(&mut self, key: &str, value: Vec<u8>) {
+        self.defaults
+            .insert(key.to_owned(), DefaultValue::Binary(value));
+    }
 }
 
 impl From<DecodeError> for PreferencesError {
@@ -180,10 +425,19 @@ impl From<PreferencesError> for EncryptedPreferencesError {
     }
 }
 
+impl From<EncryptedPreferencesError> for PreferencesError {
+    fn from(e: EncryptedPreferencesError) -> Self {
+        match e {
+            EncryptedPreferencesError::DecryptionFailed => PreferencesError::DecryptionFailed,
+            EncryptedPreferencesError::Preferences(e) => *e,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::encrypted::EncryptedPreferences;
-    use crate::preferences::Preferences;
+    use crate::encrypted::{EncryptedPreferences, EncryptedPreferencesError};
+    use crate::preferences::{Preferences, PreferencesError};
 
     #[test]
     pub fn test_creation() {
@@ -220,7 +474,7 @@ mod test {
             )
             .is_ok());
         assert!(preferences.put_bool("bool", true).is_ok());
-        assert!(preferences.put_bytes("bin", test_vec.clone()).is_ok());
+        assert!(preferences.put_binary("bin", test_vec.clone()).is_ok());
 
         // Write data to disk
         preferences.save().unwrap();
@@ -230,7 +484,7 @@ mod test {
         let i32_result = preferences.get_i32("i32");
         let str_result = preferences.get_str("str");
         let bool_result = preferences.get_bool("bool");
-        let binary_result = preferences.get_bytes("bin");
+        let binary_result = preferences.get_binary("bin");
 
         // Delete the file from the disk to avoid that some date remain on the disk if the
         // test fails.
@@ -243,4 +497,140 @@ mod test {
         assert_eq!(true, bool_result.unwrap());
         assert_eq!(test_vec, binary_result.unwrap());
     }
+
+    #[test]
+    pub fn test_change_password() {
+        let set_name = "rwenc-rotate";
+
+        let mut preferences = EncryptedPreferences::new("old-password", set_name).unwrap();
+        assert!(preferences.put_i32("i32", 7).is_ok());
+        preferences.save().unwrap();
+
+        assert!(preferences
+            .change_password("old-password", "new-password")
+            .is_ok());
+
+        // The old password no longer opens the store...
+        assert!(matches!(
+            EncryptedPreferences::new("old-password", set_name),
+            Err(EncryptedPreferencesError::DecryptionFailed)
+        ));
+
+        // ...while the new one does, and the previously stored data survived the rotation.
+        let reopened = EncryptedPreferences::new("new-password", set_name).unwrap();
+        assert_eq!(7, reopened.get_i32("i32").unwrap());
+
+        let mut preferences = EncryptedPreferences::new("new-password", set_name).unwrap();
+        preferences.erase();
+    }
+
+    #[test]
+    pub fn test_defaults() {
+        let set_name = "rwenc-defaults";
+        let password = "password";
+        let mut preferences = EncryptedPreferences::new(password, set_name).unwrap();
+
+        preferences.set_default_i32("i32", 42);
+        assert_eq!(42, preferences.get_i32("i32").unwrap());
+
+        // A stored value always takes precedence over its default.
+        preferences.put_i32("i32", 7).unwrap();
+        assert_eq!(7, preferences.get_i32("i32").unwrap());
+        preferences.set_default_str("str", "default".to_owned());
+        preferences.save().unwrap();
+
+        // Defaults aren't part of the persisted data: a fresh instance that never registered
+        // them doesn't see them, even though the stored override is still there.
+        let reloaded = EncryptedPreferences::new(password, set_name).unwrap();
+        assert_eq!(7, reloaded.get_i32("i32").unwrap());
+        assert!(reloaded.get_str("str").is_none());
+
+        preferences.erase();
+    }
+
+    #[test]
+    pub fn test_remove_and_contains() {
+        let set_name = "rwenc-remove";
+        let mut preferences = EncryptedPreferences::new("password", set_name).unwrap();
+
+        assert!(!preferences.contains("i32"));
+        preferences.put_i32("i32", 42).unwrap();
+        assert!(preferences.contains("i32"));
+
+        preferences.remove("i32");
+        assert!(!preferences.contains("i32"));
+        assert!(preferences.get_i32("i32").is_none());
+
+        preferences.erase();
+    }
+
+    #[test]
+    pub fn test_change_password_wrong_old_password() {
+        let set_name = "rwenc-rotate-wrong";
+
+        let mut preferences = EncryptedPreferences::new("password", set_name).unwrap();
+        preferences.save().unwrap();
+
+        assert!(matches!(
+            preferences.change_password("not-the-password", "new-password"),
+            Err(PreferencesError::DecryptionFailed)
+        ));
+
+        preferences.erase();
+    }
+
+    #[test]
+    pub fn test_reads_legacy_pbkdf2_store_and_upgrades_to_argon2() {
+        use crate::encrypted::Value;
+        use crate::io;
+        use chacha20poly1305::aead::{Aead, KeyInit};
+        use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+        use hmac::Hmac;
+        use pbkdf2::pbkdf2;
+        use rand::rngs::OsRng;
+        use rand::RngCore;
+        use sha2::Sha512;
+
+        let set_name = "rwenc-legacy-pbkdf2";
+        let password = "password";
+
+        // Hand-craft a store using the old `EPK1` version-1 (PBKDF2) header.
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let iterations: u32 = 100_000;
+
+        let mut key = [0u8; 32];
+        pbkdf2::<Hmac<Sha512>>(password.as_bytes(), &salt, iterations, &mut key);
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        let plaintext = bincode::serialize::<std::collections::HashMap<String, Value>>(
+            &std::collections::HashMap::new(),
+        )
+        .unwrap();
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), plaintext.as_slice())
+            .unwrap();
+
+        let mut raw = Vec::new();
+        raw.extend_from_slice(b"EPK1");
+        raw.push(1u8); // HEADER_VERSION_PBKDF2
+        raw.extend_from_slice(&salt);
+        raw.extend_from_slice(&iterations.to_le_bytes());
+        raw.extend_from_slice(&nonce_bytes);
+        raw.extend_from_slice(&ciphertext);
+
+        io::save(set_name, &base64::encode(raw), io::PreferenceKind::Config).unwrap();
+
+        // The legacy PBKDF2-encrypted store still opens...
+        let mut preferences = EncryptedPreferences::new(password, set_name).unwrap();
+        assert!(preferences.put_i32("i32", 1).is_ok());
+
+        // ...and any subsequent save re-encrypts it with Argon2id.
+        preferences.save().unwrap();
+        let reopened = EncryptedPreferences::new(password, set_name).unwrap();
+        assert_eq!(1, reopened.get_i32("i32").unwrap());
+
+        preferences.erase();
+    }
 }
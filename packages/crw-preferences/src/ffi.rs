@@ -1,13 +1,27 @@
 //! Module that provides the FFI to access the preferences from other programming languages.
 
 use crate::encrypted::EncryptedPreferences;
-use crate::preferences::Preferences;
+use crate::preferences::{is_valid_key, Preferences, PreferencesError, ValueType};
 use crate::unencrypted::UnencryptedPreferences;
 use ffi_helpers;
 use libc::{c_char, c_uchar, c_void};
+use serde::{Deserialize, Serialize};
+use serde_json::Map as JsonMap;
 use std::ptr::null_mut;
 use std::slice;
 
+/// A single preferences value carrying an explicit type tag, used to serialize a whole
+/// preferences store to JSON in [preferences_export_json] / [preferences_import_json]. Binary
+/// values are base64-encoded so the document stays valid JSON.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+enum ExportedValue {
+    I32(i32),
+    String(String),
+    Bool(bool),
+    Binary(String),
+}
+
 // Macro to export the ffi_helpers's functions used to access the error message from other programming languages.
 export_error_handling_functions!();
 
@@ -107,6 +121,39 @@ pub extern "C" fn encrypted_preferences(
     }
 }
 
+/// Changes the password protecting an encrypted preferences instance, re-encrypting its content
+/// under the new one.
+///
+/// - *preferences* pointer to the preferences instance.
+/// - *old_password* the password currently protecting the preferences.
+/// - *new_password* the password that will protect the preferences from now on.
+///
+/// Returns 0 on success, -2 if `old_password` is wrong, or -1 on any other error (including when
+/// `preferences` wasn't created with [encrypted_preferences]).
+#[no_mangle]
+pub extern "C" fn encrypted_preferences_change_password(
+    preferences: *mut c_void,
+    old_password: *const c_char,
+    new_password: *const c_char,
+) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let old_password = check_str!(old_password, -1);
+    let new_password = check_str!(new_password, -1);
+    let preferences = unsafe { unwrap_ptr_mut(preferences).as_mut().unwrap() };
+
+    match preferences.change_password(old_password, new_password) {
+        Ok(_) => 0,
+        Err(PreferencesError::DecryptionFailed) => -2,
+        Err(e) => {
+            ffi_helpers::update_last_error(e);
+            -1
+        }
+    }
+}
+
 /// Release all the resources owned by a preferences instance.
 #[no_mangle]
 pub extern "C" fn preferences_free(preferences: *mut c_void) {
@@ -175,6 +222,30 @@ pub extern "C" fn preferences_put_i32(
     }
 }
 
+/// Registers the fallback returned by [preferences_get_i32] when `key` has no value in the live
+/// store. Defaults live only in memory and are never persisted.
+///
+/// - *preferences* pointer to the preferences where the default will be registered.
+/// - *key* name of the preference the default applies to.
+/// - *value* the fallback value.
+///
+/// Returns 0 on success or -1 on error.
+#[no_mangle]
+pub extern "C" fn preferences_set_default_i32(
+    preferences: *mut c_void,
+    key: *const c_char,
+    value: i32,
+) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let key = check_str!(key, -1);
+    let preferences = unsafe { unwrap_ptr_mut(preferences).as_mut().unwrap() };
+    preferences.set_default_i32(key, value);
+    0
+}
+
 /// Gets a string from the preferences.
 ///
 /// - *preferences* pointer to the preferences from which will be extracted the value.
@@ -243,6 +314,31 @@ pub extern "C" fn preferences_put_string(
     }
 }
 
+/// Registers the fallback returned by [preferences_get_string] when `key` has no value in the
+/// live store. Defaults live only in memory and are never persisted.
+///
+/// - *preferences* pointer to the preferences where the default will be registered.
+/// - *key* name of the preference the default applies to.
+/// - *value* the fallback value.
+///
+/// Returns 0 on success or -1 on error.
+#[no_mangle]
+pub extern "C" fn preferences_set_default_string(
+    preferences: *mut c_void,
+    key: *const c_char,
+    value: *const c_char,
+) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let key = check_str!(key, -1);
+    let value = check_str!(value, -1);
+    let preferences = unsafe { unwrap_ptr_mut(preferences).as_mut().unwrap() };
+    preferences.set_default_str(key, value.to_owned());
+    0
+}
+
 /// Gets a bool from the preferences.
 ///
 /// - *preferences* pointer to the preferences from which will be extracted the value.
@@ -300,6 +396,30 @@ pub extern "C" fn preferences_put_bool(
     }
 }
 
+/// Registers the fallback returned by [preferences_get_bool] when `key` has no value in the live
+/// store. Defaults live only in memory and are never persisted.
+///
+/// - *preferences* pointer to the preferences where the default will be registered.
+/// - *key* name of the preference the default applies to.
+/// - *value* the fallback value.
+///
+/// Returns 0 on success or -1 on error.
+#[no_mangle]
+pub extern "C" fn preferences_set_default_bool(
+    preferences: *mut c_void,
+    key: *const c_char,
+    value: bool,
+) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let key = check_str!(key, -1);
+    let preferences = unsafe { unwrap_ptr_mut(preferences).as_mut().unwrap() };
+    preferences.set_default_bool(key, value);
+    0
+}
+
 /// Gets an array of bytes from the preferences.
 ///
 /// - *preferences* pointer to the preferences from which will be extracted the value.
@@ -368,6 +488,141 @@ pub extern "C" fn preferences_put_binary(
     }
 }
 
+/// Registers the fallback returned by [preferences_get_binary] when `key` has no value in the
+/// live store. Defaults live only in memory and are never persisted.
+///
+/// - *preferences* pointer to the preferences where the default will be registered.
+/// - *key* name of the preference the default applies to.
+/// - *value* array that will be used as the fallback.
+/// - *len* length of `value`.
+///
+/// Returns 0 on success or -1 on error.
+#[no_mangle]
+pub extern "C" fn preferences_set_default_binary(
+    preferences: *mut c_void,
+    key: *const c_char,
+    value: *const u8,
+    len: usize,
+) -> i32 {
+    if preferences.is_null() || value.is_null() {
+        return -1;
+    }
+
+    let key = check_str!(key, -1);
+    let preferences = unsafe { unwrap_ptr_mut(preferences).as_mut().unwrap() };
+    let value = unsafe { slice::from_raw_parts(value, len) };
+    preferences.set_default_binary(key, value.to_owned());
+    0
+}
+
+/// Returns the number of keys currently stored in `preferences`, or -1 on error.
+#[no_mangle]
+pub extern "C" fn preferences_keys_count(preferences: *const c_void) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let preferences = unsafe { unwrap_ptr(preferences).as_ref().unwrap() };
+    preferences.keys().len() as i32
+}
+
+/// Gets the name of the key at `index`, in the stable order returned by the underlying store.
+///
+/// - *preferences* pointer to the preferences instance.
+/// - *index* position of the key to retrieve, in `[0, preferences_keys_count)`.
+/// - *out_buf* pointer where will be stored the key name.
+/// - *len* maximum number of bytes that can be used from `out_buf`.
+///
+/// Returns the number of bytes that would have been written if `out_buf` had been sufficiently
+/// large, or -1 if `preferences` is invalid or `index` is out of bounds.
+#[no_mangle]
+pub extern "C" fn preferences_key_at(
+    preferences: *const c_void,
+    index: usize,
+    out_buf: *mut c_uchar,
+    len: usize,
+) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let preferences = unsafe { unwrap_ptr(preferences).as_ref().unwrap() };
+
+    match preferences.keys().get(index) {
+        Some(key) => {
+            let bytes = key.as_bytes();
+            if bytes.len() <= len {
+                let out_slice: &mut [u8] =
+                    unsafe { slice::from_raw_parts_mut(out_buf as *mut u8, bytes.len()) };
+                out_slice.copy_from_slice(bytes);
+            }
+            bytes.len() as i32
+        }
+        None => -1,
+    }
+}
+
+/// Returns a tagged code describing the type of the value stored under `key`: 0 = absent,
+/// 1 = i32, 2 = string, 3 = bool, 4 = binary.
+///
+/// Returns -1 if `preferences` or `key` is invalid.
+#[no_mangle]
+pub extern "C" fn preferences_value_type(preferences: *const c_void, key: *const c_char) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let key = check_str!(key, -1);
+    let preferences = unsafe { unwrap_ptr(preferences).as_ref().unwrap() };
+
+    match preferences.value_type(key) {
+        ValueType::Absent => 0,
+        ValueType::I32 => 1,
+        ValueType::String => 2,
+        ValueType::Bool => 3,
+        ValueType::Binary => 4,
+    }
+}
+
+/// Removes the value stored under `key`, if any.
+///
+/// - *preferences* pointer to the preferences instance.
+/// - *key* name of the preference to remove.
+///
+/// Returns 0 on success or -1 on error.
+#[no_mangle]
+pub extern "C" fn preferences_remove(preferences: *mut c_void, key: *const c_char) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let key = check_str!(key, -1);
+    let preferences = unsafe { unwrap_ptr_mut(preferences).as_mut().unwrap() };
+    preferences.remove(key);
+    0
+}
+
+/// Tests whether a value is currently stored under `key`, without fetching it.
+///
+/// - *preferences* pointer to the preferences instance.
+/// - *key* name of the preference to test.
+///
+/// Returns 1 if `key` is present, 0 if it isn't, or -2 if `preferences` or `key` is invalid.
+#[no_mangle]
+pub extern "C" fn preferences_contains(preferences: *const c_void, key: *const c_char) -> i32 {
+    if preferences.is_null() {
+        return -2;
+    }
+
+    let key = check_str!(key, -2);
+    let preferences = unsafe { unwrap_ptr(preferences).as_ref().unwrap() };
+    if preferences.contains(key) {
+        1
+    } else {
+        0
+    }
+}
+
 /// Delete all the preferences currently loaded from the provided preferences instance.
 ///
 /// - *preferences* pointer to the preferences instance.
@@ -401,6 +656,129 @@ pub extern "C" fn preferences_erase(preferences: *mut c_void) -> i32 {
     0
 }
 
+/// Serializes the whole content of `preferences` to a JSON document, so it can be backed up and
+/// later restored with [preferences_import_json]. For an encrypted preferences instance the
+/// exported document contains the decrypted plaintext values, so the caller is responsible for
+/// securing the resulting buffer.
+///
+/// - *preferences* pointer to the preferences instance to export.
+/// - *out_buf* pointer where will be stored the JSON document.
+/// - *len* maximum number of bytes that can be used from `out_buf`.
+///
+/// Returns the number of bytes that would have been written if `out_buf` had been sufficiently
+/// large, or -1 if `preferences` is invalid or the document could not be serialized.
+#[no_mangle]
+pub extern "C" fn preferences_export_json(
+    preferences: *const c_void,
+    out_buf: *mut c_uchar,
+    len: usize,
+) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let preferences = unsafe { unwrap_ptr(preferences).as_ref().unwrap() };
+
+    let mut exported = JsonMap::new();
+    for key in preferences.keys() {
+        let value = match preferences.value_type(&key) {
+            ValueType::Absent => continue,
+            ValueType::I32 => ExportedValue::I32(preferences.get_i32(&key).unwrap()),
+            ValueType::String => ExportedValue::String(preferences.get_str(&key).unwrap()),
+            ValueType::Bool => ExportedValue::Bool(preferences.get_bool(&key).unwrap()),
+            ValueType::Binary => {
+                ExportedValue::Binary(base64::encode(preferences.get_binary(&key).unwrap()))
+            }
+        };
+
+        match serde_json::to_value(&value) {
+            Ok(v) => {
+                exported.insert(key, v);
+            }
+            Err(e) => {
+                ffi_helpers::update_last_error(e);
+                return -1;
+            }
+        }
+    }
+
+    let json = match serde_json::to_string(&exported) {
+        Ok(json) => json,
+        Err(e) => {
+            ffi_helpers::update_last_error(e);
+            return -1;
+        }
+    };
+
+    let bytes = json.as_bytes();
+    if bytes.len() <= len {
+        let out_slice: &mut [u8] = unsafe { slice::from_raw_parts_mut(out_buf, bytes.len()) };
+        out_slice.copy_from_slice(bytes);
+    }
+    bytes.len() as i32
+}
+
+/// Restores into `preferences` the content of a JSON document previously produced by
+/// [preferences_export_json]. Each entry is validated before being inserted: the key must
+/// respect the usual key naming restriction and the value must carry one of the known type tags.
+///
+/// - *preferences* pointer to the preferences instance that will receive the imported values.
+/// - *json* the JSON document to import.
+///
+/// Returns 0 on success or -1 if `preferences` is invalid, `json` is not a valid UTF-8 string, or
+/// the document is malformed.
+#[no_mangle]
+pub extern "C" fn preferences_import_json(preferences: *mut c_void, json: *const c_char) -> i32 {
+    if preferences.is_null() {
+        return -1;
+    }
+
+    let json = check_str!(json, -1);
+    let imported: JsonMap<String, serde_json::Value> = match serde_json::from_str(json) {
+        Ok(map) => map,
+        Err(e) => {
+            ffi_helpers::update_last_error(e);
+            return -1;
+        }
+    };
+
+    let preferences = unsafe { unwrap_ptr_mut(preferences).as_mut().unwrap() };
+    for (key, value) in imported {
+        if !is_valid_key(&key) {
+            ffi_helpers::update_last_error(PreferencesError::InvalidName(key));
+            return -1;
+        }
+
+        let value: ExportedValue = match serde_json::from_value(value) {
+            Ok(v) => v,
+            Err(e) => {
+                ffi_helpers::update_last_error(e);
+                return -1;
+            }
+        };
+
+        let put_result = match value {
+            ExportedValue::I32(i) => preferences.put_i32(&key, i),
+            ExportedValue::String(s) => preferences.put_str(&key, s),
+            ExportedValue::Bool(b) => preferences.put_bool(&key, b),
+            ExportedValue::Binary(b) => match base64::decode(b) {
+                Ok(bin) => preferences.put_binary(&key, bin),
+                Err(e) => {
+                    ffi_helpers::update_last_error(e);
+                    return -1;
+                }
+            },
+        };
+
+        if let Err(e) = put_result {
+            ffi_helpers::update_last_error(e);
+            return -1;
+        }
+    }
+
+    0
+}
+
 /// Saves the preferences into the device disk.
 ///
 /// - *preferences* pointer to the preferences instance.
@@ -425,10 +803,14 @@ pub extern "C" fn preferences_save(preferences: *mut c_void) -> i32 {
 #[cfg(test)]
 mod tests {
     use crate::ffi::{
-        encrypted_preferences, preferences, preferences_erase, preferences_free,
-        preferences_get_binary, preferences_get_bool, preferences_get_i32, preferences_get_string,
-        preferences_put_binary, preferences_put_bool, preferences_put_i32, preferences_put_string,
-        preferences_save,
+        encrypted_preferences, encrypted_preferences_change_password, preferences,
+        preferences_erase, preferences_export_json, preferences_free, preferences_get_binary,
+        preferences_get_bool, preferences_get_i32, preferences_get_string,
+        preferences_contains, preferences_import_json, preferences_key_at,
+        preferences_keys_count, preferences_put_binary, preferences_put_bool,
+        preferences_put_i32, preferences_put_string, preferences_remove, preferences_save,
+        preferences_set_default_binary, preferences_set_default_bool, preferences_set_default_i32,
+        preferences_set_default_string, preferences_value_type,
     };
     use std::ffi::CString;
 
@@ -456,6 +838,50 @@ mod tests {
         preferences_free(raw_preferences);
     }
 
+    #[test]
+    fn test_encrypted_preferences_change_password() {
+        let preferences_name = CString::new("encrypted-rotate").unwrap();
+        let old_password = CString::new("old-password").unwrap();
+        let new_password = CString::new("new-password").unwrap();
+        let wrong_password = CString::new("wrong-password").unwrap();
+
+        let raw_preferences =
+            encrypted_preferences(preferences_name.as_ptr(), old_password.as_ptr());
+        assert!(!raw_preferences.is_null());
+
+        let i32_key = CString::new("i32").unwrap();
+        preferences_put_i32(raw_preferences, i32_key.as_ptr(), 99);
+        preferences_save(raw_preferences);
+
+        let wrong_rc = encrypted_preferences_change_password(
+            raw_preferences,
+            wrong_password.as_ptr(),
+            new_password.as_ptr(),
+        );
+        assert_eq!(-2, wrong_rc);
+
+        let change_rc = encrypted_preferences_change_password(
+            raw_preferences,
+            old_password.as_ptr(),
+            new_password.as_ptr(),
+        );
+        assert_eq!(0, change_rc);
+
+        preferences_free(raw_preferences);
+
+        let raw_reopened =
+            encrypted_preferences(preferences_name.as_ptr(), new_password.as_ptr());
+        assert!(!raw_reopened.is_null());
+
+        let mut read_val = 0;
+        let read_rc = preferences_get_i32(raw_reopened, i32_key.as_ptr(), &mut read_val);
+        assert_eq!(0, read_rc);
+        assert_eq!(99, read_val);
+
+        preferences_erase(raw_reopened);
+        preferences_free(raw_reopened);
+    }
+
     #[test]
     fn test_put_i32() {
         let preferences_name = CString::new("ffi").unwrap();
@@ -563,4 +989,203 @@ mod tests {
 
         preferences_free(raw_preferences);
     }
+
+    #[test]
+    fn test_keys_and_value_type() {
+        let preferences_name = CString::new("keys").unwrap();
+        let raw_preferences = preferences(preferences_name.as_ptr());
+        assert!(!raw_preferences.is_null());
+
+        let i32_key = CString::new("i32").unwrap();
+        preferences_put_i32(raw_preferences, i32_key.as_ptr(), 42);
+        let str_key = CString::new("str").unwrap();
+        preferences_put_string(
+            raw_preferences,
+            str_key.as_ptr(),
+            CString::new("value").unwrap().as_ptr(),
+        );
+
+        assert_eq!(2, preferences_keys_count(raw_preferences));
+
+        let mut key_buf = [0u8; 32];
+        let len = preferences_key_at(raw_preferences, 0, key_buf.as_mut_ptr(), key_buf.len());
+        assert!(len > 0);
+        let first_key = String::from_utf8(key_buf[0..len as usize].to_vec()).unwrap();
+        assert_eq!("i32", first_key);
+
+        assert_eq!(1, preferences_value_type(raw_preferences, i32_key.as_ptr()));
+        assert_eq!(2, preferences_value_type(raw_preferences, str_key.as_ptr()));
+        assert_eq!(
+            0,
+            preferences_value_type(raw_preferences, CString::new("missing").unwrap().as_ptr())
+        );
+        assert_eq!(-1, preferences_key_at(raw_preferences, 5, key_buf.as_mut_ptr(), key_buf.len()));
+
+        let erase_rc = preferences_erase(raw_preferences);
+        assert_eq!(0, erase_rc);
+
+        preferences_free(raw_preferences);
+    }
+
+    #[test]
+    fn test_defaults() {
+        let preferences_name = CString::new("defaults").unwrap();
+        let raw_preferences = preferences(preferences_name.as_ptr());
+        assert!(!raw_preferences.is_null());
+
+        let i32_key = CString::new("i32").unwrap();
+        let str_key = CString::new("str").unwrap();
+        let bool_key = CString::new("bool").unwrap();
+        let bin_key = CString::new("bin").unwrap();
+        let fallback_str = CString::new("fallback").unwrap();
+        let bin_default = [9u8, 8, 7];
+
+        assert_eq!(
+            0,
+            preferences_set_default_i32(raw_preferences, i32_key.as_ptr(), 42)
+        );
+        assert_eq!(
+            0,
+            preferences_set_default_string(raw_preferences, str_key.as_ptr(), fallback_str.as_ptr())
+        );
+        assert_eq!(
+            0,
+            preferences_set_default_bool(raw_preferences, bool_key.as_ptr(), true)
+        );
+        assert_eq!(
+            0,
+            preferences_set_default_binary(
+                raw_preferences,
+                bin_key.as_ptr(),
+                bin_default.as_ptr(),
+                bin_default.len()
+            )
+        );
+
+        let mut i32_val = 0;
+        assert_eq!(
+            0,
+            preferences_get_i32(raw_preferences, i32_key.as_ptr(), &mut i32_val)
+        );
+        assert_eq!(42, i32_val);
+
+        let mut str_buf = [0u8; 32];
+        let str_len = preferences_get_string(
+            raw_preferences,
+            str_key.as_ptr(),
+            str_buf.as_mut_ptr(),
+            str_buf.len(),
+        );
+        assert_eq!(8, str_len);
+        assert_eq!(
+            "fallback",
+            String::from_utf8(str_buf[0..str_len as usize].to_vec()).unwrap()
+        );
+
+        // An explicitly stored value still takes precedence over the registered default.
+        preferences_put_i32(raw_preferences, i32_key.as_ptr(), 7);
+        assert_eq!(
+            0,
+            preferences_get_i32(raw_preferences, i32_key.as_ptr(), &mut i32_val)
+        );
+        assert_eq!(7, i32_val);
+
+        preferences_erase(raw_preferences);
+        preferences_free(raw_preferences);
+    }
+
+    #[test]
+    fn test_remove_and_contains() {
+        let preferences_name = CString::new("remove").unwrap();
+        let raw_preferences = preferences(preferences_name.as_ptr());
+        assert!(!raw_preferences.is_null());
+
+        let key = CString::new("i32").unwrap();
+        assert_eq!(0, preferences_contains(raw_preferences, key.as_ptr()));
+
+        preferences_put_i32(raw_preferences, key.as_ptr(), 42);
+        assert_eq!(1, preferences_contains(raw_preferences, key.as_ptr()));
+
+        assert_eq!(0, preferences_remove(raw_preferences, key.as_ptr()));
+        assert_eq!(0, preferences_contains(raw_preferences, key.as_ptr()));
+
+        let mut read_val = 0;
+        assert_eq!(-1, preferences_get_i32(raw_preferences, key.as_ptr(), &mut read_val));
+
+        preferences_erase(raw_preferences);
+        preferences_free(raw_preferences);
+    }
+
+    #[test]
+    fn test_export_import_json() {
+        let preferences_name = CString::new("export").unwrap();
+        let raw_preferences = preferences(preferences_name.as_ptr());
+        assert!(!raw_preferences.is_null());
+
+        preferences_put_i32(raw_preferences, CString::new("i32").unwrap().as_ptr(), 42);
+        preferences_put_string(
+            raw_preferences,
+            CString::new("str").unwrap().as_ptr(),
+            CString::new("value").unwrap().as_ptr(),
+        );
+        preferences_put_bool(raw_preferences, CString::new("bool").unwrap().as_ptr(), true);
+        let bin = [1u8, 2, 4, 5];
+        preferences_put_binary(
+            raw_preferences,
+            CString::new("bin").unwrap().as_ptr(),
+            bin.as_ptr(),
+            bin.len(),
+        );
+
+        let mut json_buf = [0u8; 512];
+        let export_rc =
+            preferences_export_json(raw_preferences, json_buf.as_mut_ptr(), json_buf.len());
+        assert!(export_rc > 0);
+        let json = String::from_utf8(json_buf[0..export_rc as usize].to_vec()).unwrap();
+
+        let imported_name = CString::new("export-imported").unwrap();
+        let raw_imported = preferences(imported_name.as_ptr());
+        assert!(!raw_imported.is_null());
+
+        let json_c = CString::new(json).unwrap();
+        let import_rc = preferences_import_json(raw_imported, json_c.as_ptr());
+        assert_eq!(0, import_rc);
+
+        let mut i32_val = 0;
+        assert_eq!(
+            0,
+            preferences_get_i32(raw_imported, CString::new("i32").unwrap().as_ptr(), &mut i32_val)
+        );
+        assert_eq!(42, i32_val);
+
+        let mut bool_val = false;
+        assert_eq!(
+            0,
+            preferences_get_bool(
+                raw_imported,
+                CString::new("bool").unwrap().as_ptr(),
+                &mut bool_val
+            )
+        );
+        assert_eq!(true, bool_val);
+
+        let mut bin_buf = [0u8; 10];
+        let bin_len = preferences_get_binary(
+            raw_imported,
+            CString::new("bin").unwrap().as_ptr(),
+            bin_buf.as_mut_ptr(),
+            bin_buf.len(),
+        );
+        assert_eq!(4, bin_len);
+        assert_eq!(bin, bin_buf[0..4]);
+
+        // Importing a document with an invalid key must fail and leave nothing inserted.
+        let invalid_json = CString::new(r#"{"bad key":{"type":"i32","value":1}}"#).unwrap();
+        assert_eq!(-1, preferences_import_json(raw_imported, invalid_json.as_ptr()));
+
+        preferences_erase(raw_preferences);
+        preferences_free(raw_preferences);
+        preferences_erase(raw_imported);
+        preferences_free(raw_imported);
+    }
 }
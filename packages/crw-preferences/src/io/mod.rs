@@ -7,20 +7,29 @@
 //! * ios
 //! * wasm32 on browser
 
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::io::{Error as StdIoError, Error};
+use std::sync::Mutex;
 use thiserror::Error;
 #[cfg(not(target_arch = "wasm32"))]
 mod native;
 #[cfg(not(target_arch = "wasm32"))]
-use native as sys;
+use native::NativeBackend as DefaultBackend;
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use native::set_preferences_app_dir;
+pub use native::{set_additional_search_path, set_preferences_app_dir};
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "js",))]
 mod wasm;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "js"))]
-use wasm as sys;
+use wasm::WasmBackend as DefaultBackend;
+
+#[cfg(feature = "s3-backend")]
+mod s3;
+#[cfg(feature = "s3-backend")]
+pub use s3::S3Backend;
 
 /// Struct that represents a generic I/O error.
 #[derive(Error, Debug)]
@@ -39,12 +48,61 @@ pub enum IoError {
     Write,
     #[error("i/o operation not supported `{0}`")]
     Unsupported(String),
+    #[error("error while serializing the data: {0}")]
+    Serialize(String),
+    #[error("error while deserializing the data: {0}")]
+    Deserialize(String),
+    #[error("error while migrating the data from a legacy location: {0}")]
+    Migration(String),
     #[error("unknown i/o error `{0}`")]
     Unknown(String),
 }
 
 pub type Result<T> = std::result::Result<T, IoError>;
 
+/// Where a preferences set is actually read from and written to. [load]/[save]/[erase]/[exist]
+/// dispatch to whichever backend is currently active (see [set_storage_backend]), which lets a
+/// host app swap the compile-time-default native/wasm storage for something like an
+/// S3-compatible [S3Backend] at runtime, e.g. to sync an [crate::encrypted::EncryptedPreferences]
+/// set across devices. Because a `name` has already been validated by the free functions below by
+/// the time it reaches a backend, implementors don't need to re-check it.
+pub trait StorageBackend: Send + Sync {
+    /// See [load].
+    fn load(&self, name: &str, kind: PreferenceKind) -> Result<String>;
+    /// See [save].
+    fn save(&self, name: &str, data: &str, kind: PreferenceKind) -> Result<()>;
+    /// See [erase].
+    fn erase(&self, name: &str, kind: PreferenceKind);
+    /// See [exist].
+    fn exist(&self, name: &str, kind: PreferenceKind) -> bool;
+}
+
+/// The [StorageBackend] currently used by [load]/[save]/[erase]/[exist], defaulting to the
+/// platform's native filesystem backend (or the browser `LocalStorage` one on wasm).
+static ACTIVE_BACKEND: Lazy<Mutex<Box<dyn StorageBackend>>> =
+    Lazy::new(|| Mutex::new(Box::new(DefaultBackend)));
+
+/// Registers the [StorageBackend] used by every subsequent [load]/[save]/[erase]/[exist] call,
+/// replacing the platform default. Useful for pointing the wallet at an [S3Backend] (or any other
+/// custom backend) for cross-device sync.
+pub fn set_storage_backend(backend: Box<dyn StorageBackend>) {
+    *ACTIVE_BACKEND.lock().unwrap() = backend;
+}
+
+/// Which base directory a preferences file is resolved under, following the XDG base directory
+/// split (`$XDG_CONFIG_HOME`/`$XDG_DATA_HOME`/`$XDG_CACHE_HOME` on Linux, the equivalent
+/// Application Support / Known Folder locations on macOS and Windows). On Android and iOS all
+/// kinds resolve to the same app-provided absolute path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferenceKind {
+    /// User configuration, via `dirs::config_dir`.
+    Config,
+    /// User application data, via `dirs::data_dir`.
+    Data,
+    /// Non-essential cached data, via `dirs::cache_dir`.
+    Cache,
+}
+
 /// Functions to check if a key used to access the storage is valid.
 ///
 /// * `name` - The that will be checked.
@@ -65,9 +123,9 @@ fn is_name_valid(name: &str) -> bool {
 /// * [IoError::Read] - if an error occurred while reading the data from the device storage
 /// * [IoError::EmptyData] - if the data associated to the provided `name` is empty
 /// * [IoError::Unsupported] - if the device don't supports this operation
-pub fn load(name: &str) -> Result<String> {
+pub fn load(name: &str, kind: PreferenceKind) -> Result<String> {
     if is_name_valid(name) {
-        sys::load(name)
+        ACTIVE_BACKEND.lock().unwrap().load(name, kind)
     } else {
         Err(IoError::InvalidName(name.to_owned()))
     }
@@ -83,28 +141,28 @@ pub fn load(name: &str) -> Result<String> {
 /// This function can returns one of the following errors:
 /// * [IoError::Write] - if an error occur while writing the data into the device storage
 /// * [IoError::Unsupported] - if the device don't supports this operation
-pub fn save(name: &str, data: &str) -> Result<()> {
+pub fn save(name: &str, data: &str, kind: PreferenceKind) -> Result<()> {
     if is_name_valid(name) {
-        sys::save(name, data)
+        ACTIVE_BACKEND.lock().unwrap().save(name, data, kind)
     } else {
         Err(IoError::InvalidName(name.to_owned()))
     }
 }
 
 /// Erase a preferences set stored into the device memory.
-pub fn erase(name: &str) {
+pub fn erase(name: &str, kind: PreferenceKind) {
     if is_name_valid(name) {
-        sys::erase(name)
+        ACTIVE_BACKEND.lock().unwrap().erase(name, kind)
     }
 }
 
 /// Check if exist a preferences set withe the provided name into the device storage.
-pub fn exist(name: &str) -> bool {
+pub fn exist(name: &str, kind: PreferenceKind) -> bool {
     if !is_name_valid(name) {
         return false;
     }
 
-    sys::exist(name)
+    ACTIVE_BACKEND.lock().unwrap().exist(name, kind)
 }
 
 impl From<StdIoError> for IoError {
@@ -112,3 +170,80 @@ impl From<StdIoError> for IoError {
         IoError::Std(e)
     }
 }
+
+/// On-disk encoding used by [load_as] and [save_from].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferenceFormat {
+    /// Compact JSON, via `serde_json`.
+    Json,
+    /// Human-readable, pretty-printed TOML, via `toml`.
+    TomlPretty,
+}
+
+impl PreferenceFormat {
+    fn serialize<T: Serialize>(self, value: &T) -> Result<String> {
+        match self {
+            PreferenceFormat::Json => {
+                serde_json::to_string(value).map_err(|e| IoError::Serialize(e.to_string()))
+            }
+            PreferenceFormat::TomlPretty => {
+                toml::to_string_pretty(value).map_err(|e| IoError::Serialize(e.to_string()))
+            }
+        }
+    }
+
+    fn deserialize<T: DeserializeOwned>(self, data: &str) -> Result<T> {
+        match self {
+            PreferenceFormat::Json => {
+                serde_json::from_str(data).map_err(|e| IoError::Deserialize(e.to_string()))
+            }
+            PreferenceFormat::TomlPretty => {
+                toml::from_str(data).map_err(|e| IoError::Deserialize(e.to_string()))
+            }
+        }
+    }
+}
+
+/// The format used by [load_as]/[save_from] when no [PreferenceFormat] is given explicitly.
+static DEFAULT_FORMAT: Lazy<Mutex<PreferenceFormat>> =
+    Lazy::new(|| Mutex::new(PreferenceFormat::Json));
+
+/// Sets the module-level default [PreferenceFormat] used by [load_as]/[save_from].
+pub fn set_default_format(format: PreferenceFormat) {
+    *DEFAULT_FORMAT.lock().unwrap() = format;
+}
+
+fn default_format() -> PreferenceFormat {
+    *DEFAULT_FORMAT.lock().unwrap()
+}
+
+/// Loads a preferences set and deserializes it as `T`, using `format` if given or the
+/// module-level default format otherwise.
+///
+/// # Errors
+/// In addition to [load]'s errors, this function returns [IoError::Deserialize] if `data` is not
+/// valid `format`-encoded `T`.
+pub fn load_as<T: DeserializeOwned>(
+    name: &str,
+    kind: PreferenceKind,
+    format: Option<PreferenceFormat>,
+) -> Result<T> {
+    let data = load(name, kind)?;
+    format.unwrap_or_else(default_format).deserialize(&data)
+}
+
+/// Serializes `value` and saves it as a preferences set, using `format` if given or the
+/// module-level default format otherwise.
+///
+/// # Errors
+/// In addition to [save]'s errors, this function returns [IoError::Serialize] if `value` can't
+/// be encoded as `format`.
+pub fn save_from<T: Serialize>(
+    name: &str,
+    value: &T,
+    kind: PreferenceKind,
+    format: Option<PreferenceFormat>,
+) -> Result<()> {
+    let data = format.unwrap_or_else(default_format).serialize(value)?;
+    save(name, &data, kind)
+}
@@ -6,11 +6,13 @@
 //! * android
 //! * ios
 
-use crate::io::{IoError, Result};
+use crate::io::{IoError, PreferenceKind, Result, StorageBackend};
+use fs2::FileExt;
 use once_cell::sync::Lazy;
 use std::fs;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::path::PathBuf;
+use std::process;
 use std::sync::Mutex;
 
 /// Global variable that contains the directory name where will be stored the configurations files.
@@ -19,12 +21,15 @@ static PREFERENCES_APP_DIR: Lazy<Mutex<String>> = Lazy::new(|| {
     Mutex::new(str)
 });
 
-/// Gets a file with the provided name from the application configuration directory.
+/// Gets a file with the provided name from the application configuration, data or cache
+/// directory, depending on `kind`.
 ///
 /// * `name` - the name of the file requested from the user.
 /// * `create` - if true creates also the file if not exist.
+/// * `kind` - which base directory (config, data or cache) the file lives under. Ignored on
+///   Android/iOS, where a single app-provided absolute path is used for every kind.
 ///
-/// The file is located inside the application config directory that depends on the target device OS.
+/// The file is located inside the application directory that depends on the target device OS.
 ///
 /// |Platform | Example                                                                                                    |
 /// | ------- | -----------------------------------------------------------------------------------------------------------|
@@ -37,7 +42,7 @@ static PREFERENCES_APP_DIR: Lazy<Mutex<String>> = Lazy::new(|| {
 /// # Errors
 ///
 /// This function return an [std::io::Error] if the file can't be created inside the configuration directory.
-fn get_config_file(name: &str, create: bool) -> Result<PathBuf> {
+fn get_config_file(name: &str, create: bool, kind: PreferenceKind) -> Result<PathBuf> {
     cfg_if! {
         if #[cfg(test)] {
             // In test mode just use the current working directory.
@@ -45,7 +50,8 @@ fn get_config_file(name: &str, create: bool) -> Result<PathBuf> {
         }
         else if #[cfg(any(target_os = "android", target_os = "ios"))] {
             // On android or ios we can't obtain the path at runtime so the app dir must be an
-            // absolute path to the directory where will be stored the configurations.
+            // absolute path to the directory where will be stored the configurations, regardless
+            // of `kind`.
             let dir = PREFERENCES_APP_DIR.lock().unwrap();
             if dir.is_empty() {
                 return Err(IoError::EmptyPreferencesPath);
@@ -58,8 +64,13 @@ fn get_config_file(name: &str, create: bool) -> Result<PathBuf> {
             if dir.is_empty() {
                 return Err(IoError::EmptyPreferencesPath);
             }
-            let mut config_dir = dirs::config_dir().unwrap();
-            // Append the binary name to the default config dir
+            let base_dir = match kind {
+                PreferenceKind::Config => dirs::config_dir(),
+                PreferenceKind::Data => dirs::data_dir(),
+                PreferenceKind::Cache => dirs::cache_dir(),
+            };
+            let mut config_dir = base_dir.unwrap();
+            // Append the binary name to the base dir
             config_dir.push(dir.as_str());
             // Check if the directory exists, if not create it.
             if !config_dir.exists() {
@@ -70,6 +81,9 @@ fn get_config_file(name: &str, create: bool) -> Result<PathBuf> {
 
     // Append the name provided from the user
     config_dir.push(name);
+
+    migrate_if_needed(name, &config_dir, kind)?;
+
     // Check if the file exists, if not create an empty one.
     if create && !config_dir.exists() {
         File::create(config_dir.as_path())?;
@@ -78,6 +92,60 @@ fn get_config_file(name: &str, create: bool) -> Result<PathBuf> {
     Ok(config_dir)
 }
 
+/// Directories a preference file may have lived in before the current canonical location was
+/// introduced, checked (in order) by [migrate_if_needed]. Before the XDG config/data/cache split
+/// every preference file, regardless of [PreferenceKind], was stored under `dirs::config_dir()`.
+fn legacy_dirs() -> Vec<PathBuf> {
+    let app_dir = PREFERENCES_APP_DIR.lock().unwrap();
+    if app_dir.is_empty() {
+        return Vec::new();
+    }
+
+    dirs::config_dir()
+        .into_iter()
+        .map(|mut dir| {
+            dir.push(app_dir.as_str());
+            dir
+        })
+        .collect()
+}
+
+/// One-time migration pass: if `canonical` does not exist yet but the same `name` is found under
+/// one of [legacy_dirs], move it to `canonical` (atomic rename, falling back to copy+remove when
+/// the legacy and canonical paths live on different filesystems).
+///
+/// Whether or not anything was found, a marker file is written next to `canonical` so the legacy
+/// directories are scanned at most once per `name`.
+fn migrate_if_needed(name: &str, canonical: &PathBuf, kind: PreferenceKind) -> Result<()> {
+    // Config-kind files already lived at this exact path before the split; nothing to migrate.
+    if kind == PreferenceKind::Config || canonical.exists() {
+        return Ok(());
+    }
+
+    let marker = canonical.with_file_name(format!(".{}.migrated", name));
+    if marker.exists() {
+        return Ok(());
+    }
+
+    for legacy_dir in legacy_dirs() {
+        let legacy_path = legacy_dir.join(name);
+        if legacy_path != *canonical && legacy_path.exists() {
+            if let Some(parent) = canonical.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::rename(&legacy_path, canonical)
+                .or_else(|_| fs::copy(&legacy_path, canonical).and_then(|_| fs::remove_file(&legacy_path)))
+                .map_err(|e| IoError::Migration(e.to_string()))?;
+            break;
+        }
+    }
+
+    // Record that the scan ran, so it isn't repeated even if nothing was found to migrate.
+    let _ = File::create(&marker);
+    Ok(())
+}
+
 /// Sets the application directory where will be stored the configurations.
 /// On windows, macOS and linux `dir` should be only the name of the directory that will
 /// be create inside the current user app configurations directory.
@@ -89,6 +157,79 @@ pub fn set_preferences_app_dir(dir: &str) {
     str.push_str(dir);
 }
 
+/// Additional directory that reads ([load]/[exist]) check before falling back to the OS-default
+/// directory. Lets embedders point the wallet at a portable or sandbox-mounted config location
+/// (useful for testing, or for deployments that ship seed config) without recompiling to change
+/// [set_preferences_app_dir].
+static ADDITIONAL_SEARCH_PATH: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+/// Sets the additional search path checked by [load]/[exist] before the OS-default directory.
+pub fn set_additional_search_path(path: PathBuf) {
+    *ADDITIONAL_SEARCH_PATH.lock().unwrap() = Some(path);
+}
+
+/// Returns `name` resolved inside the additional search path, if one is set and the file exists
+/// there.
+fn additional_search_file(name: &str) -> Option<PathBuf> {
+    ADDITIONAL_SEARCH_PATH
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|dir| dir.join(name))
+        .filter(|path| path.exists())
+}
+
+/// Returns the path of the sentinel file [FileLock] locks on behalf of `config_file`.
+///
+/// `flock` binds to the inode of the file descriptor it's taken on, not to the path - but
+/// [save]'s atomic write replaces `config_file`'s inode via `rename`. Locking `config_file`
+/// itself would mean the lock a writer holds stops applying to the path the instant the rename
+/// happens, letting a racing reader or writer that opens the path afterward acquire a lock on
+/// the *new* inode with no contention at all. Locking this sentinel instead - which [save] never
+/// replaces - keeps every caller contending for the same inode for as long as `config_file`
+/// exists.
+fn lock_file_path(config_file: &PathBuf) -> PathBuf {
+    let name = config_file
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    config_file.with_file_name(format!(".{name}.lock"))
+}
+
+/// RAII guard around an advisory file lock, released automatically on drop.
+struct FileLock(File);
+
+impl FileLock {
+    /// Opens (creating if needed) the sentinel lock file for `config_file` and blocks until an
+    /// exclusive lock is acquired. Used to guard the whole read-modify-write path of [save] and
+    /// [erase] so concurrent processes and threads can't corrupt each other.
+    fn exclusive(config_file: &PathBuf) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(lock_file_path(config_file))?;
+        file.lock_exclusive()?;
+        Ok(FileLock(file))
+    }
+
+    /// Opens (creating if needed) the sentinel lock file for `config_file` and blocks until a
+    /// shared lock is acquired. Used to guard [load] against a concurrent writer.
+    fn shared(config_file: &PathBuf) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(lock_file_path(config_file))?;
+        file.lock_shared()?;
+        Ok(FileLock(file))
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.0.unlock();
+    }
+}
+
 /// Loads the string representation of a preferences set.
 ///
 /// * `name` - name of the file from which will be loaded the preferences.
@@ -97,8 +238,13 @@ pub fn set_preferences_app_dir(dir: &str) {
 /// This function can returns one of the following errors:
 /// * [IoError::Read] if the file with the provided `name` can't be read
 /// * [IoError::EmptyData] if the file is empty
-pub fn load(name: &str) -> Result<String> {
-    let config_file = get_config_file(name, true)?;
+pub fn load(name: &str, kind: PreferenceKind) -> Result<String> {
+    let config_file = match additional_search_file(name) {
+        Some(path) => path,
+        None => get_config_file(name, true, kind)?,
+    };
+    let _lock = FileLock::shared(&config_file)?;
+
     let content = fs::read_to_string(config_file)?;
 
     if content.is_empty() {
@@ -113,28 +259,47 @@ pub fn load(name: &str) -> Result<String> {
 /// * `name` - Name of the file where will be stored the data.
 /// * `data` - The string that will be stored inside the file.
 ///
+/// The write is made atomic by first writing `data` to a sibling temporary file, unique to this
+/// process, and then renaming it over `name`'s file. A reader can therefore never observe a
+/// half-written file.
+///
 /// # Errors
 /// This function returns [IoError::Write] if can't write to the file with the provided `name`.
-pub fn save(name: &str, data: &str) -> Result<()> {
-    let config_file = get_config_file(name, true)?;
-    fs::write(config_file, data)?;
+pub fn save(name: &str, data: &str, kind: PreferenceKind) -> Result<()> {
+    let config_file = get_config_file(name, true, kind)?;
+    let _lock = FileLock::exclusive(&config_file)?;
+
+    let tmp_file = config_file.with_file_name(format!(
+        ".{}.{}.tmp",
+        name,
+        process::id()
+    ));
+    fs::write(&tmp_file, data)?;
+    fs::rename(&tmp_file, &config_file)?;
+
     Ok(())
 }
 
 /// Deletes the file with the provide `name` from the device storage.
-pub fn erase(name: &str) {
-    let path = get_config_file(name, false);
+pub fn erase(name: &str, kind: PreferenceKind) {
+    let path = get_config_file(name, false, kind);
     if let Ok(path) = path {
         if path.exists() {
             // Make the compiler happy, an error here should never occur.
-            let _ = fs::remove_file(path);
+            if let Ok(_lock) = FileLock::exclusive(&path) {
+                let _ = fs::remove_file(path);
+            }
         }
     }
 }
 
 /// Check if exist a preferences set with the provided `name` into the device storage.
-pub fn exist(name: &str) -> bool {
-    let path = get_config_file(name, false);
+pub fn exist(name: &str, kind: PreferenceKind) -> bool {
+    if additional_search_file(name).is_some() {
+        return true;
+    }
+
+    let path = get_config_file(name, false, kind);
 
     if let Ok(p) = path {
         p.exists()
@@ -142,3 +307,82 @@ pub fn exist(name: &str) -> bool {
         false
     }
 }
+
+/// [StorageBackend] backed by the functions above, i.e. the platform's native filesystem. This is
+/// the default backend on every target except wasm.
+pub struct NativeBackend;
+
+impl StorageBackend for NativeBackend {
+    fn load(&self, name: &str, kind: PreferenceKind) -> Result<String> {
+        load(name, kind)
+    }
+
+    fn save(&self, name: &str, data: &str, kind: PreferenceKind) -> Result<()> {
+        save(name, data, kind)
+    }
+
+    fn erase(&self, name: &str, kind: PreferenceKind) {
+        erase(name, kind)
+    }
+
+    fn exist(&self, name: &str, kind: PreferenceKind) -> bool {
+        exist(name, kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    /// Writes `0..WRITES` in sequence while readers run concurrently, and checks that a read
+    /// starting after a given write has committed never observes an earlier value - i.e. the
+    /// lock handoff between [save] and [load] is actually serialized on the same file, not on an
+    /// inode [save]'s rename has since replaced.
+    #[test]
+    fn concurrent_reads_never_observe_a_write_older_than_one_they_started_after() {
+        const WRITES: u64 = 50;
+        const READERS: usize = 8;
+
+        let name = "native-concurrent-rw";
+        erase(name, PreferenceKind::Config);
+        save(name, "0", PreferenceKind::Config).unwrap();
+
+        let last_completed_write = Arc::new(AtomicU64::new(0));
+        let start = Arc::new(Barrier::new(READERS + 1));
+
+        let reader_handles: Vec<_> = (0..READERS)
+            .map(|_| {
+                let last_completed_write = Arc::clone(&last_completed_write);
+                let start = Arc::clone(&start);
+                thread::spawn(move || {
+                    start.wait();
+                    for _ in 0..(WRITES * 4) {
+                        let observed_before = last_completed_write.load(Ordering::SeqCst);
+                        let read = load(name, PreferenceKind::Config).unwrap();
+                        let value: u64 = read.parse().unwrap();
+                        assert!(
+                            value >= observed_before,
+                            "read {value} is older than the write {observed_before} that had \
+                             already completed when this read started"
+                        );
+                    }
+                })
+            })
+            .collect();
+
+        start.wait();
+        for i in 1..=WRITES {
+            save(name, &i.to_string(), PreferenceKind::Config).unwrap();
+            last_completed_write.store(i, Ordering::SeqCst);
+        }
+
+        for handle in reader_handles {
+            handle.join().unwrap();
+        }
+
+        erase(name, PreferenceKind::Config);
+    }
+}
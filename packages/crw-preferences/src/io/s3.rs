@@ -0,0 +1,161 @@
+//! [StorageBackend] that stores each preferences set as a single object in an S3-compatible
+//! bucket, usable against AWS S3 itself or a self-hosted [Garage](https://garagehq.deuxfleurs.fr/)
+//! cluster. This lets a set be pushed to and pulled from object storage for wallet sync across
+//! devices. Because [crate::encrypted::EncryptedPreferences] always encrypts the data before
+//! calling [crate::io::save], the remote object store only ever sees ciphertext.
+//!
+//! Gated behind the `s3-backend` feature since `aws-sdk-s3` is a heavy, optional dependency that
+//! most embedders of this crate won't need.
+
+use crate::io::{IoError, PreferenceKind, Result, StorageBackend};
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::operation::get_object::GetObjectError;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::{Client, Config};
+use tokio::runtime::Runtime;
+
+/// [StorageBackend] that reads and writes objects in a single S3-compatible `bucket`. Since
+/// object storage has no directory concept, `kind` is folded into the object key as a prefix
+/// instead of a base directory.
+pub struct S3Backend {
+    client: Client,
+    bucket: String,
+    /// Dedicated runtime used to block on the otherwise-async `aws-sdk-s3` calls, since
+    /// [StorageBackend] (like every other backend) is synchronous.
+    runtime: Runtime,
+}
+
+impl S3Backend {
+    /// Builds a backend that talks to `bucket` via `endpoint`.
+    ///
+    /// * `endpoint` - `None` to talk to AWS S3 itself, or `Some("https://garage.example.com")` to
+    /// point at a self-hosted Garage cluster (or any other S3-compatible endpoint).
+    /// * `region` - the bucket's region; Garage deployments commonly use a placeholder such as
+    /// `"garage"`.
+    ///
+    /// # Errors
+    /// Returns [IoError::Unknown] if the backing tokio runtime can't be created.
+    pub fn new(
+        endpoint: Option<&str>,
+        region: &str,
+        access_key_id: &str,
+        secret_access_key: &str,
+        bucket: &str,
+    ) -> Result<S3Backend> {
+        let runtime = Runtime::new().map_err(|e| IoError::Unknown(e.to_string()))?;
+
+        let credentials = Credentials::new(
+            access_key_id,
+            secret_access_key,
+            None,
+            None,
+            "crw-preferences",
+        );
+        let mut config_builder = Config::builder()
+            .region(Region::new(region.to_owned()))
+            .credentials_provider(credentials)
+            .behavior_version_latest();
+
+        if let Some(endpoint) = endpoint {
+            config_builder = config_builder
+                .endpoint_url(endpoint)
+                .force_path_style(true);
+        }
+
+        let client = Client::from_conf(config_builder.build());
+
+        Ok(S3Backend {
+            client,
+            bucket: bucket.to_owned(),
+            runtime,
+        })
+    }
+
+    /// The object key a preferences set of the given `name`/`kind` is stored under.
+    fn object_key(name: &str, kind: PreferenceKind) -> String {
+        let prefix = match kind {
+            PreferenceKind::Config => "config",
+            PreferenceKind::Data => "data",
+            PreferenceKind::Cache => "cache",
+        };
+        format!("{}/{}", prefix, name)
+    }
+}
+
+impl StorageBackend for S3Backend {
+    fn load(&self, name: &str, kind: PreferenceKind) -> Result<String> {
+        let key = S3Backend::object_key(name, kind);
+
+        self.runtime.block_on(async {
+            let output = self
+                .client
+                .get_object()
+                .bucket(&self.bucket)
+                .key(&key)
+                .send()
+                .await
+                .map_err(|e| match e.into_service_error() {
+                    GetObjectError::NoSuchKey(_) => IoError::EmptyData,
+                    other => IoError::Unknown(other.to_string()),
+                })?;
+
+            let bytes = output
+                .body
+                .collect()
+                .await
+                .map_err(|_| IoError::Read)?
+                .into_bytes();
+            let content = String::from_utf8(bytes.to_vec()).map_err(|_| IoError::Read)?;
+
+            if content.is_empty() {
+                Err(IoError::EmptyData)
+            } else {
+                Ok(content)
+            }
+        })
+    }
+
+    fn save(&self, name: &str, data: &str, kind: PreferenceKind) -> Result<()> {
+        let key = S3Backend::object_key(name, kind);
+
+        self.runtime.block_on(async {
+            self.client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(&key)
+                .body(ByteStream::from(data.as_bytes().to_vec()))
+                .send()
+                .await
+                .map_err(|_| IoError::Write)?;
+
+            Ok(())
+        })
+    }
+
+    fn erase(&self, name: &str, kind: PreferenceKind) {
+        let key = S3Backend::object_key(name, kind);
+
+        let _ = self.runtime.block_on(async {
+            self.client
+                .delete_object()
+                .bucket(&self.bucket)
+                .key(&key)
+                .send()
+                .await
+        });
+    }
+
+    fn exist(&self, name: &str, kind: PreferenceKind) -> bool {
+        let key = S3Backend::object_key(name, kind);
+
+        self.runtime.block_on(async {
+            self.client
+                .head_object()
+                .bucket(&self.bucket)
+                .key(&key)
+                .send()
+                .await
+                .is_ok()
+        })
+    }
+}
@@ -1,6 +1,6 @@
 //! Module that provides functions to read and write data from the browser local storage.
 
-use crate::io::{IoError, Result};
+use crate::io::{IoError, PreferenceKind, Result, StorageBackend};
 use web_sys::{Storage, Window};
 
 /// Gets the browser `LocalStorage` instance.
@@ -21,7 +21,10 @@ fn get_storage() -> Result<Storage> {
 /// Loads the a string from the browse `LocalStorage`.
 ///
 /// * `name` - key that uniquely identify the data that will be loaded.
-pub fn load(name: &str) -> Result<String> {
+///
+/// `LocalStorage` has no directory concept, so `kind` is ignored: every [PreferenceKind] shares
+/// the same flat key namespace.
+pub fn load(name: &str, _kind: PreferenceKind) -> Result<String> {
     let storage = get_storage()?;
 
     Storage::get_item(&storage, name)
@@ -44,14 +47,14 @@ pub fn load(name: &str) -> Result<String> {
 /// # Errors
 /// This function returns [Err(IoError::Unsupported)] if the browser don't support the LocalStorage API
 /// or [Err(IoError::Write)] if an error occur when writing the data to the browser local storage.
-pub fn save(name: &str, value: &str) -> Result<()> {
+pub fn save(name: &str, value: &str, _kind: PreferenceKind) -> Result<()> {
     let storage = get_storage()?;
 
     Storage::set_item(&storage, name, value).map_err(|_| IoError::Write)
 }
 
 /// Deletes the data from the browser `LocalStorage`
-pub fn erase(name: &str) {
+pub fn erase(name: &str, _kind: PreferenceKind) {
     let storage = get_storage();
 
     if let Ok(storage) = storage {
@@ -61,7 +64,7 @@ pub fn erase(name: &str) {
 }
 
 /// Check if is present a non empty string into the browser `LocalStorage`.
-pub fn exist(name: &str) -> bool {
+pub fn exist(name: &str, _kind: PreferenceKind) -> bool {
     let storage = get_storage();
 
     return if storage.is_err() {
@@ -78,3 +81,25 @@ pub fn exist(name: &str) -> bool {
         }
     };
 }
+
+/// [StorageBackend] backed by the functions above, i.e. the browser `LocalStorage`. This is the
+/// default backend on wasm32 targets.
+pub struct WasmBackend;
+
+impl StorageBackend for WasmBackend {
+    fn load(&self, name: &str, kind: PreferenceKind) -> Result<String> {
+        load(name, kind)
+    }
+
+    fn save(&self, name: &str, data: &str, kind: PreferenceKind) -> Result<()> {
+        save(name, data, kind)
+    }
+
+    fn erase(&self, name: &str, kind: PreferenceKind) {
+        erase(name, kind)
+    }
+
+    fn exist(&self, name: &str, kind: PreferenceKind) -> bool {
+        exist(name, kind)
+    }
+}
@@ -16,6 +16,41 @@ pub enum PreferencesError {
     DeserializationError,
     #[error("error while serializing the preferences")]
     SerializationError,
+    #[error("the provided password does not authenticate the stored data")]
+    DecryptionFailed,
+    #[error("this operation is not supported by this preferences backend")]
+    Unsupported,
+}
+
+/// Tagged code describing what kind of value (if any) is stored under a key, returned by
+/// [Preferences::value_type].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Absent,
+    I32,
+    String,
+    Bool,
+    Binary,
+}
+
+/// A value registered via [Preferences::set_default_i32] and friends, returned by the matching
+/// getter when the live store has no value under that key. Defaults are kept in memory only and
+/// are never part of the persisted file, so they can change freely between app versions.
+#[derive(Debug, Clone)]
+pub enum DefaultValue {
+    I32(i32),
+    Str(String),
+    Bool(bool),
+    Binary(Vec<u8>),
+}
+
+/// Returns true if `key` contains only ascii alphanumeric characters or `-`, `_`, mirroring the
+/// naming restriction already enforced on preferences set names.
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ['-', '_'].contains(&c))
 }
 
 /// Trait that represents a generic preference container.
@@ -64,6 +99,21 @@ pub trait Preferences {
     /// - *value* The array that will be stored into the preferences.
     fn put_binary(&mut self, key: &str, value: Vec<u8>) -> Result<()>;
 
+    /// Returns the keys currently stored, in a stable order.
+    fn keys(&self) -> Vec<String>;
+
+    /// Returns the [ValueType] of the value stored under `key`, or [ValueType::Absent] if no
+    /// value is stored there.
+    fn value_type(&self, key: &str) -> ValueType;
+
+    /// Removes the value stored under `key`, if any. Does nothing if `key` is absent.
+    fn remove(&mut self, key: &str);
+
+    /// Returns true if a value is currently stored under `key`.
+    fn contains(&self, key: &str) -> bool {
+        self.value_type(key) != ValueType::Absent
+    }
+
     /// Delete all the preferences currently loaded.
     fn clear(&mut self);
 
@@ -73,4 +123,35 @@ pub trait Preferences {
 
     /// Saves the preferences on the device disk.
     fn save(&self) -> Result<()>;
+
+    /// Replaces the password protecting this preferences set, re-encrypting its content under
+    /// the new one. Backends that aren't password-protected return
+    /// [PreferencesError::Unsupported].
+    ///
+    /// - *old_password* The password currently protecting the preferences.
+    /// - *new_password* The password that will protect the preferences from now on.
+    ///
+    /// # Errors
+    /// This function returns [PreferencesError::DecryptionFailed] if `old_password` doesn't
+    /// authenticate the data currently stored.
+    fn change_password(&mut self, old_password: &str, new_password: &str) -> Result<()> {
+        let _ = (old_password, new_password);
+        Err(PreferencesError::Unsupported)
+    }
+
+    /// Registers `value` as the fallback returned by [Preferences::get_i32] when `key` has no
+    /// value in the live store. Overwrites any default previously registered under `key`.
+    fn set_default_i32(&mut self, key: &str, value: i32);
+
+    /// Registers `value` as the fallback returned by [Preferences::get_str] when `key` has no
+    /// value in the live store. Overwrites any default previously registered under `key`.
+    fn set_default_str(&mut self, key: &str, value: String);
+
+    /// Registers `value` as the fallback returned by [Preferences::get_bool] when `key` has no
+    /// value in the live store. Overwrites any default previously registered under `key`.
+    fn set_default_bool(&mut self, key: &str, value: bool);
+
+    /// Registers `value` as the fallback returned by [Preferences::get_binary] when `key` has no
+    /// value in the live store. Overwrites any default previously registered under `key`.
+    fn set_default_binary(&mut self, key: &str, value: Vec<u8>);
 }
@@ -1,11 +1,27 @@
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
 use core::result;
+use rand::rngs::OsRng;
+use rand::RngCore;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json;
 use std::collections::HashMap;
 use std::fs::OpenOptions;
+use std::io::Read;
+use std::path::PathBuf;
 
 pub type Result<T> = result::Result<T, PreferencesError>;
 
+/// Magic bytes that mark a preferences file as encrypted with [Preferences::save_encrypted].
+const ENCRYPTED_MAGIC: &[u8; 4] = b"CRWP";
+/// Version of the encrypted file header, bumped whenever the KDF/AEAD parameters change.
+const ENCRYPTED_VERSION: u8 = 1;
+/// Length in bytes of the random salt used to derive the Argon2id key.
+const SALT_LEN: usize = 16;
+/// Length in bytes of the random nonce used by XChaCha20-Poly1305.
+const NONCE_LEN: usize = 24;
+
 #[derive(Debug)]
 pub enum PreferencesError {
     InvalidName,
@@ -13,29 +29,143 @@ pub enum PreferencesError {
     IO(String),
     DeserializationError,
     SerializationError,
+    /// The password was wrong or the encrypted file has been tampered with.
+    DecryptionError,
+}
+
+/// Where a [Preferences] set is actually persisted. `Preferences` never touches the filesystem
+/// (or any other medium) directly: it only reads and writes opaque bytes through this trait, so
+/// host apps can substitute a backend appropriate for their platform, e.g. a secure-enclave
+/// backed one on mobile, without `Preferences` itself changing at all.
+pub trait StorageBackend {
+    /// Reads the raw bytes previously stored under `name`, or `None` if nothing was ever saved.
+    fn read(&self, name: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Persists `bytes` under `name`, overwriting any previous content.
+    fn write(&self, name: &str, bytes: &[u8]) -> Result<()>;
+}
+
+/// Default [StorageBackend] that stores each preferences set as a single file.
+///
+/// With no directory configured this reproduces the historical behavior of writing to
+/// `./{name}` in the process' current directory, which keeps existing callers working
+/// unchanged. [FileSystemBackend::with_dir] can be used to point it at a platform-correct
+/// directory (e.g. one resolved via `dirs::config_dir()`) instead.
+pub struct FileSystemBackend {
+    dir: Option<PathBuf>,
+}
+
+impl FileSystemBackend {
+    /// Creates a backend that stores files in the process' current directory, matching the
+    /// behavior `Preferences` has always had.
+    pub fn new() -> FileSystemBackend {
+        FileSystemBackend { dir: None }
+    }
+
+    /// Creates a backend that stores files inside `dir`. The directory is created on the first
+    /// write if it doesn't exist yet.
+    pub fn with_dir(dir: PathBuf) -> FileSystemBackend {
+        FileSystemBackend { dir: Some(dir) }
+    }
+
+    fn path_for(&self, name: &str) -> PathBuf {
+        match &self.dir {
+            Some(dir) => dir.join(name),
+            None => PathBuf::from(format!("./{}", name)),
+        }
+    }
+}
+
+impl Default for FileSystemBackend {
+    fn default() -> Self {
+        FileSystemBackend::new()
+    }
+}
+
+impl StorageBackend for FileSystemBackend {
+    fn read(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path_for(name);
+        let read_result = OpenOptions::new().read(true).open(&path);
+
+        let mut file = match read_result {
+            Ok(file) => file,
+            Err(_) => return Ok(None),
+        };
+
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)
+            .map_err(|e| PreferencesError::IO(e.to_string()))?;
+
+        Ok(Some(content))
+    }
+
+    fn write(&self, name: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.path_for(name);
+
+        if let Some(dir) = &self.dir {
+            std::fs::create_dir_all(dir).map_err(|e| PreferencesError::IO(e.to_string()))?;
+        }
+
+        std::fs::write(&path, bytes).map_err(|e| PreferencesError::IO(e.to_string()))
+    }
+}
+
+/// A [StorageBackend] that delegates reads and writes to host-app-provided callbacks, letting
+/// the platform layer (Android Keystore, iOS Keychain, ...) hold the secret in a secure enclave
+/// where it never reaches a readable file. `Preferences` stays oblivious to how the host
+/// actually secures the data.
+pub struct KeystoreBackend {
+    read_fn: Box<dyn Fn(&str) -> Result<Option<Vec<u8>>> + Send + Sync>,
+    write_fn: Box<dyn Fn(&str, &[u8]) -> Result<()> + Send + Sync>,
+}
+
+impl KeystoreBackend {
+    /// Builds a backend around the given host callbacks.
+    pub fn new(
+        read_fn: impl Fn(&str) -> Result<Option<Vec<u8>>> + Send + Sync + 'static,
+        write_fn: impl Fn(&str, &[u8]) -> Result<()> + Send + Sync + 'static,
+    ) -> KeystoreBackend {
+        KeystoreBackend {
+            read_fn: Box::new(read_fn),
+            write_fn: Box::new(write_fn),
+        }
+    }
+}
+
+impl StorageBackend for KeystoreBackend {
+    fn read(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        (self.read_fn)(name)
+    }
+
+    fn write(&self, name: &str, bytes: &[u8]) -> Result<()> {
+        (self.write_fn)(name, bytes)
+    }
 }
 
 pub struct Preferences {
     name: String,
     data: HashMap<String, String>,
+    backend: Box<dyn StorageBackend>,
 }
 
 impl Preferences {
-    fn load_from_disk(name: &str) -> Result<HashMap<String, String>> {
+    fn validate_name(name: &str) -> Result<()> {
         if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
             return Err(PreferencesError::InvalidName);
         }
 
-        // TODO: Handle read in a multiplatform environment
-        let path = format!("./{}", name);
-        let read_result = OpenOptions::new().read(true).open(&path);
+        Ok(())
+    }
 
-        if read_result.is_err() {
-            return Ok(HashMap::new());
-        }
+    fn load_from_disk(name: &str, backend: &dyn StorageBackend) -> Result<HashMap<String, String>> {
+        Preferences::validate_name(name)?;
 
-        let file = read_result.unwrap();
-        let data: HashMap<String, String> = if let Ok(data) = serde_json::from_reader(file) {
+        let content = match backend.read(name)? {
+            Some(content) => content,
+            None => return Ok(HashMap::new()),
+        };
+
+        let data: HashMap<String, String> = if let Ok(data) = serde_json::from_slice(&content) {
             data
         } else {
             HashMap::new()
@@ -44,33 +174,174 @@ impl Preferences {
         Ok(data)
     }
 
-    fn write_to_disk(name: &str, data: &HashMap<String, String>) -> Result<()> {
-        // TODO: Handle write in a multiplatform environment
-        let path = format!("./{}", name);
-        let out_file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .open(&path)
-            .map_err(|_e| PreferencesError::IO(_e.to_string()))?;
+    fn write_to_disk(
+        name: &str,
+        data: &HashMap<String, String>,
+        backend: &dyn StorageBackend,
+    ) -> Result<()> {
+        let serialized = serde_json::to_vec(data).map_err(|_e| PreferencesError::SerializationError)?;
+
+        backend.write(name, &serialized)
+    }
 
-        serde_json::to_writer(out_file, &data)
+    /// Derives a 32-byte symmetric key from `password` and `salt` using Argon2id
+    /// (64 MiB memory, 3 iterations, 1 degree of parallelism).
+    fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32]> {
+        let params = argon2::Params::new(64 * 1024, 3, 1, Some(32))
             .map_err(|_e| PreferencesError::SerializationError)?;
+        let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
 
-        Ok(())
+        let mut key = [0u8; 32];
+        argon2
+            .hash_password_into(password.as_bytes(), salt, &mut key)
+            .map_err(|_e| PreferencesError::SerializationError)?;
+
+        Ok(key)
+    }
+
+    fn load_from_disk_encrypted(
+        name: &str,
+        password: &str,
+        backend: &dyn StorageBackend,
+    ) -> Result<HashMap<String, String>> {
+        Preferences::validate_name(name)?;
+
+        let content = match backend.read(name)? {
+            Some(content) => content,
+            None => return Ok(HashMap::new()),
+        };
+
+        if content.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let header_len = ENCRYPTED_MAGIC.len() + 1 + SALT_LEN + NONCE_LEN;
+        if content.len() < header_len || &content[..ENCRYPTED_MAGIC.len()] != ENCRYPTED_MAGIC {
+            return Err(PreferencesError::DecryptionError);
+        }
+
+        let mut offset = ENCRYPTED_MAGIC.len();
+        let version = content[offset];
+        offset += 1;
+        if version != ENCRYPTED_VERSION {
+            return Err(PreferencesError::DecryptionError);
+        }
+
+        let salt = &content[offset..offset + SALT_LEN];
+        offset += SALT_LEN;
+        let nonce = &content[offset..offset + NONCE_LEN];
+        offset += NONCE_LEN;
+        let ciphertext = &content[offset..];
+
+        let key = Preferences::derive_key(password, salt)?;
+        let cipher = XChaCha20Poly1305::new_from_slice(&key)
+            .map_err(|_e| PreferencesError::DecryptionError)?;
+
+        let plaintext = cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_e| PreferencesError::DecryptionError)?;
+
+        serde_json::from_slice(&plaintext).map_err(|_e| PreferencesError::DeserializationError)
     }
 
-    /// Creates a new [Preferences] instance.
+    fn write_to_disk_encrypted(
+        name: &str,
+        data: &HashMap<String, String>,
+        password: &str,
+        backend: &dyn StorageBackend,
+    ) -> Result<()> {
+        let serialized =
+            serde_json::to_vec(data).map_err(|_e| PreferencesError::SerializationError)?;
+
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce_bytes);
+
+        let key = Preferences::derive_key(password, &salt)?;
+        let cipher = XChaCha20Poly1305::new_from_slice(&key)
+            .map_err(|_e| PreferencesError::SerializationError)?;
+        let nonce = XNonce::from_slice(&nonce_bytes);
+        let ciphertext = cipher
+            .encrypt(nonce, serialized.as_slice())
+            .map_err(|_e| PreferencesError::SerializationError)?;
+
+        let mut out = Vec::with_capacity(ENCRYPTED_MAGIC.len() + 1 + SALT_LEN + NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(ENCRYPTED_MAGIC);
+        out.push(ENCRYPTED_VERSION);
+        out.extend_from_slice(&salt);
+        out.extend_from_slice(&nonce_bytes);
+        out.extend_from_slice(&ciphertext);
+
+        backend.write(name, &out)
+    }
+
+    /// Creates a new [Preferences] instance backed by the default [FileSystemBackend], i.e.
+    /// reading/writing `./{name}` in the process' current directory.
     ///
     /// * `name` - The preferences name, can contains only ascii alphanumeric chars.
     pub fn new(name: &str) -> Result<Preferences> {
-        let data = Preferences::load_from_disk(name)?;
+        Preferences::with_backend(name, Box::new(FileSystemBackend::new()))
+    }
+
+    /// Creates a new [Preferences] instance that reads and writes through `backend` instead of
+    /// the default filesystem location, letting host apps (and tests) substitute their own
+    /// storage medium.
+    ///
+    /// * `name` - The preferences name, can contains only ascii alphanumeric chars.
+    /// * `backend` - The [StorageBackend] used to persist this preferences set.
+    pub fn with_backend(name: &str, backend: Box<dyn StorageBackend>) -> Result<Preferences> {
+        let data = Preferences::load_from_disk(name, backend.as_ref())?;
 
         Ok(Preferences {
             name: name.to_owned(),
             data,
+            backend,
         })
     }
 
+    /// Creates a new [Preferences] instance whose content is protected at rest with
+    /// password-based authenticated encryption (Argon2id + XChaCha20-Poly1305), backed by the
+    /// default [FileSystemBackend].
+    ///
+    /// * `name` - The preferences name, can contains only ascii alphanumeric chars.
+    /// * `password` - The password used to encrypt/decrypt the preferences file.
+    ///
+    /// # Errors
+    /// Returns [PreferencesError::DecryptionError] if `password` is wrong or the file has
+    /// been tampered with.
+    pub fn new_encrypted(name: &str, password: &str) -> Result<Preferences> {
+        Preferences::with_backend_encrypted(name, password, Box::new(FileSystemBackend::new()))
+    }
+
+    /// Creates a new [Preferences] instance whose content is protected at rest with
+    /// password-based authenticated encryption, reading and writing through `backend`.
+    ///
+    /// * `name` - The preferences name, can contains only ascii alphanumeric chars.
+    /// * `password` - The password used to encrypt/decrypt the preferences file.
+    /// * `backend` - The [StorageBackend] used to persist this preferences set.
+    pub fn with_backend_encrypted(
+        name: &str,
+        password: &str,
+        backend: Box<dyn StorageBackend>,
+    ) -> Result<Preferences> {
+        let data = Preferences::load_from_disk_encrypted(name, password, backend.as_ref())?;
+
+        Ok(Preferences {
+            name: name.to_owned(),
+            data,
+            backend,
+        })
+    }
+
+    /// Reads a plaintext preferences file written by [Preferences::save] and rewrites it
+    /// encrypted with `password`, so it can subsequently be opened with [Preferences::new_encrypted].
+    pub fn migrate_to_encrypted(name: &str, password: &str) -> Result<()> {
+        let backend = FileSystemBackend::new();
+        let data = Preferences::load_from_disk(name, &backend)?;
+        Preferences::write_to_disk_encrypted(name, &data, password, &backend)
+    }
+
     /// Retrive a value from the preferences.
     ///
     /// * `key` - The name of the preference to retrieve.
@@ -101,16 +372,23 @@ impl Preferences {
         Ok(())
     }
 
-    /// Saves the data on the device disk
+    /// Saves the data through this instance's backend.
     pub fn save(&self) -> Result<()> {
-        return Preferences::write_to_disk(&self.name, &self.data);
+        Preferences::write_to_disk(&self.name, &self.data, self.backend.as_ref())
+    }
+
+    /// Saves the data through this instance's backend, encrypted with `password`.
+    pub fn save_encrypted(&self, password: &str) -> Result<()> {
+        Preferences::write_to_disk_encrypted(&self.name, &self.data, password, self.backend.as_ref())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::storage::Preferences;
+    use crate::storage::{FileSystemBackend, KeystoreBackend, Preferences, StorageBackend};
+    use std::collections::HashMap;
     use std::fs;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     pub fn test_preferences_save() {
@@ -145,4 +423,78 @@ mod tests {
         assert!(Preferences::new("test").is_ok());
         assert!(Preferences::new("test1").is_ok());
     }
+
+    #[test]
+    pub fn test_encrypted_preferences_save_and_load() {
+        let preference_name = "testenc";
+
+        let mut test_preferences = Preferences::new_encrypted(preference_name, "password").unwrap();
+        test_preferences
+            .put("data", &"some simple data".to_string())
+            .unwrap();
+        test_preferences.save_encrypted("password").unwrap();
+
+        let loaded = Preferences::new_encrypted(preference_name, "password").unwrap();
+        assert_eq!("some simple data", loaded.get::<String>("data").unwrap());
+
+        // A wrong password must fail to decrypt instead of returning garbage.
+        assert!(Preferences::new_encrypted(preference_name, "wrong").is_err());
+
+        fs::remove_file(preference_name).unwrap();
+    }
+
+    #[test]
+    pub fn test_migrate_to_encrypted() {
+        let preference_name = "testmigrate";
+
+        let mut plain = Preferences::new(preference_name).unwrap();
+        plain.put("data", &"plaintext value".to_string()).unwrap();
+        plain.save().unwrap();
+
+        Preferences::migrate_to_encrypted(preference_name, "password").unwrap();
+
+        let migrated = Preferences::new_encrypted(preference_name, "password").unwrap();
+        assert_eq!("plaintext value", migrated.get::<String>("data").unwrap());
+
+        fs::remove_file(preference_name).unwrap();
+    }
+
+    #[test]
+    pub fn test_with_dir_backend() {
+        let dir = std::env::temp_dir().join("crw_preferences_storage_test_with_dir");
+        let backend = FileSystemBackend::with_dir(dir.clone());
+
+        let mut prefs = Preferences::with_backend("testdirbackend", Box::new(backend)).unwrap();
+        prefs.put("data", &"in a custom dir".to_string()).unwrap();
+        prefs.save().unwrap();
+
+        assert!(dir.join("testdirbackend").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_keystore_backend() {
+        let store: Arc<Mutex<HashMap<String, Vec<u8>>>> = Arc::new(Mutex::new(HashMap::new()));
+
+        let read_store = Arc::clone(&store);
+        let write_store = Arc::clone(&store);
+
+        let backend = KeystoreBackend::new(
+            move |name| Ok(read_store.lock().unwrap().get(name).cloned()),
+            move |name, bytes| {
+                write_store
+                    .lock()
+                    .unwrap()
+                    .insert(name.to_owned(), bytes.to_owned());
+                Ok(())
+            },
+        );
+
+        let mut prefs = Preferences::with_backend("testkeystore", Box::new(backend)).unwrap();
+        prefs.put("data", &"secret data".to_string()).unwrap();
+        prefs.save().unwrap();
+
+        assert!(store.lock().unwrap().contains_key("testkeystore"));
+    }
 }
@@ -3,12 +3,49 @@
 
 use crate::io;
 use crate::io::IoError;
-use crate::preferences::{Preferences, PreferencesError, Result};
+use crate::preferences::{DefaultValue, Preferences, PreferencesError, Result, ValueType};
 use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Key under which a binary value is tagged in the underlying JSON document, so it can't be
+/// confused with a user-provided [Preferences::put_str] value. See [encode_binary]/[decode_binary].
+const BINARY_TAG_KEY: &str = "$bin";
+
+/// Encodes `value` as `{ "$bin": "<base64>" }`, the compact encoding written by
+/// [Preferences::put_binary] going forward.
+fn encode_binary(value: &[u8]) -> Value {
+    let mut tagged = Map::with_capacity(1);
+    tagged.insert(
+        BINARY_TAG_KEY.to_owned(),
+        Value::from(base64::encode(value)),
+    );
+    Value::Object(tagged)
+}
+
+/// Decodes a value previously written by [encode_binary], or (for preferences files written
+/// before this format existed) a legacy JSON array of byte-sized numbers.
+fn decode_binary(value: &Value) -> Option<Vec<u8>> {
+    if let Some(base64_str) = value
+        .as_object()
+        .and_then(|obj| obj.get(BINARY_TAG_KEY))
+        .and_then(|tag| tag.as_str())
+    {
+        return base64::decode(base64_str).ok();
+    }
+
+    value.as_array().map(|array| {
+        array
+            .iter()
+            .filter_map(|element| element.as_u64())
+            .map(|n| n as u8)
+            .collect()
+    })
+}
 
 pub struct UnencryptedPreferences {
     name: String,
     data: Map<String, Value>,
+    defaults: HashMap<String, DefaultValue>,
 }
 
 impl UnencryptedPreferences {
@@ -21,7 +58,7 @@ impl UnencryptedPreferences {
     /// * [PreferencesError::DeserializationError] if the data loaded from the disk is not valid.
     /// * [PreferencesError::IO] if an error occurred while reading the data from the device storage.
     fn load_from_disk(name: &str) -> Result<Map<String, Value>> {
-        let disk_data = io::load(name);
+        let disk_data = io::load(name, io::PreferenceKind::Config);
 
         if disk_data.is_err() {
             let err = disk_data.err().unwrap();
@@ -48,7 +85,7 @@ impl UnencryptedPreferences {
     fn write_to_disk(name: &str, data: &Map<String, Value>) -> Result<()> {
         let json =
             serde_json::to_string(&data).map_err(|_| PreferencesError::SerializationError)?;
-        io::save(name, &json)?;
+        io::save(name, &json, io::PreferenceKind::Config)?;
 
         Ok(())
     }
@@ -69,21 +106,28 @@ impl UnencryptedPreferences {
         Ok(UnencryptedPreferences {
             name: name.to_owned(),
             data,
+            defaults: HashMap::new(),
         })
     }
 }
 
 impl Preferences for UnencryptedPreferences {
     fn get_i32(&self, key: &str) -> Option<i32> {
-        self.data.get(key).and_then(|v| {
-            v.as_i64().and_then(|i| {
-                if i >= (i32::MIN as i64) && i <= (i32::MAX as i64) {
-                    Some(i as i32)
-                } else {
-                    None
-                }
+        self.data
+            .get(key)
+            .and_then(|v| {
+                v.as_i64().and_then(|i| {
+                    if i >= (i32::MIN as i64) && i <= (i32::MAX as i64) {
+                        Some(i as i32)
+                    } else {
+                        None
+                    }
+                })
+            })
+            .or_else(|| match self.defaults.get(key) {
+                Some(DefaultValue::I32(v)) => Some(*v),
+                _ => None,
             })
-        })
     }
 
     fn put_i32(&mut self, key: &str, value: i32) -> Result<()> {
@@ -95,6 +139,10 @@ impl Preferences for UnencryptedPreferences {
         self.data
             .get(key)
             .and_then(|v| v.as_str().map(|s| s.to_owned()))
+            .or_else(|| match self.defaults.get(key) {
+                Some(DefaultValue::Str(v)) => Some(v.clone()),
+                _ => None,
+            })
     }
 
     fn put_str(&mut self, key: &str, value: String) -> Result<()> {
@@ -103,7 +151,13 @@ impl Preferences for UnencryptedPreferences {
     }
 
     fn get_bool(&self, key: &str) -> Option<bool> {
-        self.data.get(key).and_then(|v| v.as_bool())
+        self.data
+            .get(key)
+            .and_then(|v| v.as_bool())
+            .or_else(|| match self.defaults.get(key) {
+                Some(DefaultValue::Bool(v)) => Some(*v),
+                _ => None,
+            })
     }
 
     fn put_bool(&mut self, key: &str, value: bool) -> Result<()> {
@@ -111,39 +165,77 @@ impl Preferences for UnencryptedPreferences {
         Ok(())
     }
 
-    fn get_bytes(&self, key: &str) -> Option<Vec<u8>> {
-        self.data.get(key).and_then(|v| v.as_array()).map(|v| {
-            let mut vector: Vec<u8> = Vec::with_capacity(v.len());
-            for value in v {
-                if value.is_u64() {
-                    vector.push(value.as_u64().unwrap() as u8)
-                }
-            }
-            vector
-        })
+    fn get_binary(&self, key: &str) -> Option<Vec<u8>> {
+        self.data
+            .get(key)
+            .and_then(decode_binary)
+            .or_else(|| match self.defaults.get(key) {
+                Some(DefaultValue::Binary(v)) => Some(v.clone()),
+                _ => None,
+            })
     }
 
-    fn put_bytes(&mut self, key: &str, value: Vec<u8>) -> Result<()> {
-        self.data.insert(key.to_owned(), Value::from(value));
+    fn put_binary(&mut self, key: &str, value: Vec<u8>) -> Result<()> {
+        self.data.insert(key.to_owned(), encode_binary(&value));
         Ok(())
     }
 
+    fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.data.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn value_type(&self, key: &str) -> ValueType {
+        match self.data.get(key) {
+            None => ValueType::Absent,
+            Some(v) if v.is_string() => ValueType::String,
+            Some(v) if v.is_boolean() => ValueType::Bool,
+            Some(v) if v.is_i64() || v.is_u64() => ValueType::I32,
+            Some(v) if v.is_array() || v.is_object() => ValueType::Binary,
+            Some(_) => ValueType::Absent,
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.data.remove(key);
+    }
+
     fn clear(&mut self) {
         self.data.clear()
     }
 
     fn erase(&mut self) {
         self.clear();
-        io::erase(&self.name)
+        io::erase(&self.name, io::PreferenceKind::Config)
     }
 
     fn save(&self) -> Result<()> {
         UnencryptedPreferences::write_to_disk(&self.name, &self.data)
     }
+
+    fn set_default_i32(&mut self, key: &str, value: i32) {
+        self.defaults.insert(key.to_owned(), DefaultValue::I32(value));
+    }
+
+    fn set_default_str(&mut self, key: &str, value: String) {
+        self.defaults.insert(key.to_owned(), DefaultValue::Str(value));
+    }
+
+    fn set_default_bool(&mut self, key: &str, value: bool) {
+        self.defaults
+            .insert(key.to_owned(), DefaultValue::Bool(value));
+    }
+
+    fn set_default_binary(&mut self, key: &str, value: Vec<u8>) {
+        self.defaults
+            .insert(key.to_owned(), DefaultValue::Binary(value));
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::io;
     use crate::preferences;
     use crate::preferences::Preferences;
     use crate::unencrypted::UnencryptedPreferences;
@@ -183,7 +275,7 @@ mod tests {
         assert!(preferences.put_i32("i32", 42).is_ok());
         assert!(preferences.put_str("str", "str".to_owned()).is_ok());
         assert!(preferences.put_bool("bool", true).is_ok());
-        assert!(preferences.put_bytes("bin", test_vec.clone()).is_ok());
+        assert!(preferences.put_binary("bin", test_vec.clone()).is_ok());
 
         // Write data to disk
         preferences.save().unwrap();
@@ -193,7 +285,7 @@ mod tests {
         let i32_result = preferences.get_i32("i32");
         let str_result = preferences.get_str("str");
         let bool_result = preferences.get_bool("bool");
-        let binary_result = preferences.get_bytes("bin");
+        let binary_result = preferences.get_binary("bin");
 
         // Delete the file from the disk to avoid that some date remain on the disk if the
         // test fails.
@@ -204,6 +296,80 @@ mod tests {
         assert_eq!(test_vec, binary_result.unwrap());
     }
 
+    #[test]
+    pub fn test_defaults() {
+        let set_name = "unencrypted-defaults";
+        let mut preferences = UnencryptedPreferences::new(set_name).unwrap();
+
+        preferences.set_default_i32("i32", 42);
+        preferences.set_default_str("str", "fallback".to_owned());
+        preferences.set_default_bool("bool", true);
+        preferences.set_default_binary("bin", vec![1, 2, 3]);
+
+        // The defaults are returned while the keys are absent from the live store...
+        assert_eq!(42, preferences.get_i32("i32").unwrap());
+        assert_eq!("fallback", preferences.get_str("str").unwrap());
+        assert_eq!(true, preferences.get_bool("bool").unwrap());
+        assert_eq!(vec![1, 2, 3], preferences.get_binary("bin").unwrap());
+
+        // ...but a stored value always takes precedence over its default.
+        preferences.put_i32("i32", 7).unwrap();
+        assert_eq!(7, preferences.get_i32("i32").unwrap());
+        preferences.save().unwrap();
+
+        // Defaults aren't part of the persisted data: a fresh instance that never registered
+        // them doesn't see them, even though the stored override is still there.
+        let reloaded = UnencryptedPreferences::new(set_name).unwrap();
+        assert_eq!(7, reloaded.get_i32("i32").unwrap());
+        assert!(reloaded.get_str("str").is_none());
+
+        preferences.erase();
+    }
+
+    #[test]
+    pub fn test_remove_and_contains() {
+        let set_name = "unencrypted-remove";
+        let mut preferences = UnencryptedPreferences::new(set_name).unwrap();
+
+        assert!(!preferences.contains("i32"));
+        preferences.put_i32("i32", 42).unwrap();
+        assert!(preferences.contains("i32"));
+
+        preferences.remove("i32");
+        assert!(!preferences.contains("i32"));
+        assert!(preferences.get_i32("i32").is_none());
+
+        // Removing an already-absent key is a no-op.
+        preferences.remove("i32");
+
+        preferences.erase();
+    }
+
+    #[test]
+    pub fn test_binary_migrates_legacy_number_array() {
+        let set_name = "unencrypted-legacy-binary";
+
+        // Simulate a file written before binary values were base64-tagged: a plain JSON array
+        // of byte-sized numbers under the key.
+        io::save(
+            set_name,
+            r#"{"bin":[12,13,54,42]}"#,
+            io::PreferenceKind::Config,
+        )
+        .unwrap();
+
+        let mut preferences = UnencryptedPreferences::new(set_name).unwrap();
+        assert_eq!(vec![12, 13, 54, 42], preferences.get_binary("bin").unwrap());
+
+        // Saving it back re-encodes it in the new compact format.
+        preferences.put_binary("bin", vec![12, 13, 54, 42]).unwrap();
+        preferences.save().unwrap();
+        let reloaded = UnencryptedPreferences::new(set_name).unwrap();
+        assert_eq!(vec![12, 13, 54, 42], reloaded.get_binary("bin").unwrap());
+
+        preferences.erase();
+    }
+
     #[test]
     pub fn test_exist() {
         let set_name = "unencrypted-exist";
@@ -47,12 +47,12 @@ impl PreferencesWrapper {
 
     #[wasm_bindgen(js_name = "getBytes")]
     pub fn get_bytes(&self, key: &str) -> Option<Vec<u8>> {
-        self.container.get_bytes(key)
+        self.container.get_binary(key)
     }
 
     #[wasm_bindgen(js_name = "putBytes")]
     pub fn put_bytes(&mut self, key: &str, value: Vec<u8>) -> Result<(), JsValue> {
-        Ok(self.container.put_bytes(key, value)?)
+        Ok(self.container.put_binary(key, value)?)
     }
 
     pub fn clear(&mut self) {
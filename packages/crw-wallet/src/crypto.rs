@@ -4,19 +4,233 @@
 //! sign a generic [`Vec<u8>`] payload.
 
 use crate::WalletError;
+use aes::Aes128;
 use bech32::{ToBase32, Variant::Bech32};
 use bip39::{Language, Mnemonic, MnemonicType, Seed};
 use bitcoin::{
     network::constants::Network,
     secp256k1::Secp256k1,
-    util::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey},
-    PublicKey,
+    util::bip32::{
+        ChainCode, ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, Fingerprint,
+    },
+    PrivateKey, PublicKey,
 };
+use ctr::cipher::{NewCipher, StreamCipher};
+use ctr::Ctr128BE;
+use generic_array::GenericArray;
 use hdpath::StandardHDPath;
-use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+use k256::ecdsa::{recoverable, signature::Signer, signature::Verifier, Signature, SigningKey, VerifyingKey};
+use pem::Pem;
+use rand::rngs::OsRng;
+use rand::RngCore;
 use ripemd160::Ripemd160;
+use scrypt::{scrypt, Params as ScryptParams};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use sha3::{digest::Digest as _, Keccak256};
 use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Range;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Arc, Mutex};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+use uuid::Uuid;
+
+/// AES-128 in CTR mode, the cipher used by the Web3 Secret Storage keystore format.
+type Aes128Ctr = Ctr128BE<Aes128>;
+
+/// `scrypt` cost parameter (as `log2(n)`) used by [MnemonicWallet::to_keystore]. Existing
+/// keystores are read back with whatever `n`/`r`/`p` they were written with, so this only governs
+/// newly exported ones.
+const KEYSTORE_SCRYPT_LOG_N: u8 = 13;
+const KEYSTORE_SCRYPT_R: u32 = 8;
+const KEYSTORE_SCRYPT_P: u32 = 1;
+const KEYSTORE_DKLEN: usize = 32;
+const KEYSTORE_VERSION: u8 = 3;
+
+/// Tag byte prepended to a [Keystore]'s decrypted payload, identifying what follows it.
+const KEYSTORE_SECRET_MNEMONIC: u8 = 0;
+const KEYSTORE_SECRET_RAW_KEY: u8 = 1;
+
+/// PEM tag used by [MnemonicWallet::to_pem]/[MnemonicWallet::from_pem].
+const PEM_TAG: &str = "COSMOS PRIVATE KEY";
+
+/// Web3 Secret Storage ("UTC/JSON keystore") encoding of a [MnemonicWallet]'s mnemonic phrase
+/// (or of its raw private key, for a wallet without one), compatible with the format produced by other Cosmos/Ethereum SDKs. The derivation path is
+/// deliberately not part of this document: it's supplied again to [MnemonicWallet::from_keystore]
+/// by the caller.
+#[derive(Serialize, Deserialize)]
+struct Keystore {
+    version: u8,
+    id: String,
+    crypto: KeystoreCrypto,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    ciphertext: String,
+    cipherparams: KeystoreCipherParams,
+    kdf: String,
+    kdfparams: KeystoreKdfParams,
+    mac: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreCipherParams {
+    iv: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreKdfParams {
+    dklen: u8,
+    n: u32,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+/// Derives a `dklen`-byte key from `password` and `salt` with scrypt, using cost parameters
+/// `n = 2^log_n`.
+fn derive_scrypt_key(
+    password: &str,
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> Result<[u8; KEYSTORE_DKLEN], WalletError> {
+    let params = ScryptParams::new(log_n, r, p, KEYSTORE_DKLEN)
+        .map_err(|err| WalletError::Keystore(err.to_string()))?;
+    let mut key = [0u8; KEYSTORE_DKLEN];
+    scrypt(password.as_bytes(), salt, &params, &mut key)
+        .map_err(|err| WalletError::Keystore(err.to_string()))?;
+    Ok(key)
+}
+
+/// Encrypts/decrypts `data` in place with AES-128-CTR under `key`/`iv` (CTR mode is its own
+/// inverse).
+fn apply_aes128_ctr(key: &[u8], iv: &[u8], data: &mut [u8]) {
+    let mut cipher = Aes128Ctr::new(
+        GenericArray::from_slice(key),
+        GenericArray::from_slice(iv),
+    );
+    cipher.apply_keystream(data);
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Computes the bech32 address for `pub_key` under the given human readable part, as described
+/// in [MnemonicWallet::get_bech32_address].
+fn bech32_address_from_pub_key(pub_key: &PublicKey, hrp: &str) -> Result<String, WalletError> {
+    let mut hasher = Sha256::new();
+    hasher.update(pub_key.to_bytes());
+
+    // Read hash digest over the public key bytes & consume hasher
+    let pk_hash = hasher.finalize();
+
+    // Insert the hash result in the ripdem hash function
+    let mut rip_hasher = Ripemd160::new();
+    rip_hasher.update(pk_hash);
+    let rip_result = rip_hasher.finalize();
+
+    let address_bytes = rip_result.to_vec();
+
+    bech32::encode(hrp, address_bytes.to_base32(), Bech32)
+        .map_err(|err| WalletError::Hrp(err.to_string()))
+}
+
+/// Recovers the bech32 address, under `hrp`, of the Secp256k1 key that produced
+/// `recoverable_sig` over `msg`. Mirrors OpenEthereum's `ethkey::brain_recover`, recast for Cosmos
+/// bech32 addresses instead of Ethereum's keccak-derived ones.
+///
+/// `recoverable_sig` is a 65-byte recoverable ECDSA signature: a 64-byte `r || s` signature
+/// followed by a 1-byte recovery id, the format produced by [`k256::ecdsa::recoverable::Signature`].
+///
+/// # Errors
+/// Returns [WalletError::Verify] if `recoverable_sig` is malformed or no public key can be
+/// recovered from it, or [WalletError::Hrp] if `hrp` is invalid.
+pub fn recover_address(
+    msg: &[u8],
+    recoverable_sig: &[u8],
+    hrp: &str,
+) -> Result<String, WalletError> {
+    let signature = recoverable::Signature::try_from(recoverable_sig)
+        .map_err(|err| WalletError::Verify(err.to_string()))?;
+
+    let verify_key = signature
+        .recover_verify_key(msg)
+        .map_err(|err| WalletError::Verify(err.to_string()))?;
+
+    let pub_key = PublicKey::from_slice(&verify_key.to_bytes())
+        .map_err(|err| WalletError::Verify(err.to_string()))?;
+
+    bech32_address_from_pub_key(&pub_key, hrp)
+}
+
+/// A pattern matched against a bech32 address's data part - everything after the hrp's `1`
+/// separator - by [MnemonicWallet::generate_vanity]. Mirrors the prefix/suffix/charset knobs
+/// OpenEthereum's `ethkey` exposes via `BrainPrefix`/`Prefix` for Ethereum vanity keys, recast for
+/// Cosmos bech32 addresses.
+#[derive(Clone)]
+pub enum VanityPattern {
+    /// Matches addresses whose data part starts with this literal string.
+    Prefix(String),
+    /// Matches addresses whose data part ends with this literal string.
+    Suffix(String),
+    /// Matches addresses whose data part is made up entirely of characters from this set.
+    Charset(String),
+}
+
+impl VanityPattern {
+    fn matches(&self, data_part: &str) -> bool {
+        match self {
+            VanityPattern::Prefix(prefix) => data_part.starts_with(prefix.as_str()),
+            VanityPattern::Suffix(suffix) => data_part.ends_with(suffix.as_str()),
+            VanityPattern::Charset(charset) => data_part.chars().all(|c| charset.contains(c)),
+        }
+    }
+}
+
+/// A structured BIP-44 derivation path `m/44'/coin_type'/account'/change/address_index`, for
+/// callers of [MnemonicWallet::new_bip44] that want to assemble a path from its components
+/// instead of formatting (and risking malforming) the string themselves. Mirrors the `Bip44` type
+/// the iota-sdk introduced for the same purpose.
+///
+/// The `Default` implementation is the Cosmos Hub path: coin type 118, account 0, external chain
+/// 0, index 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bip44 {
+    pub coin_type: u32,
+    pub account: u32,
+    pub change: u32,
+    pub address_index: u32,
+}
+
+impl Default for Bip44 {
+    fn default() -> Self {
+        Bip44 {
+            coin_type: 118,
+            account: 0,
+            change: 0,
+            address_index: 0,
+        }
+    }
+}
+
+impl fmt::Display for Bip44 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "m/44'/{}'/{}'/{}/{}",
+            self.coin_type, self.account, self.change, self.address_index
+        )
+    }
+}
 
 /// Represents a Secp256k1 key pair.
 #[derive(Clone)]
@@ -28,7 +242,13 @@ struct Keychain {
 /// Facility used to manage a Secp256k1 key pair and generate signatures.
 #[derive(Clone)]
 pub struct MnemonicWallet {
-    mnemonic: Mnemonic,
+    /// `None` for a wallet imported directly from key material that didn't come from a mnemonic
+    /// (see [MnemonicWallet::from_pem]/[MnemonicWallet::from_keystore]'s raw-key case).
+    mnemonic: Option<Mnemonic>,
+    /// The BIP-39 "25th word" `mnemonic`'s seed was derived with. Empty for wallets created
+    /// through [MnemonicWallet::new]/[MnemonicWallet::random]. Kept around so
+    /// [MnemonicWallet::set_derivation_path] re-derives with the same passphrase.
+    passphrase: String,
     derivation_path: String,
     keychain: Keychain,
 }
@@ -53,12 +273,29 @@ impl MnemonicWallet {
     pub fn new(
         mnemonic_phrase: &str,
         derivation_path: &str,
+    ) -> Result<MnemonicWallet, WalletError> {
+        MnemonicWallet::new_with_passphrase(mnemonic_phrase, "", derivation_path)
+    }
+
+    /// Derive a Secp256k1 key pair from the given `mnemonic_phrase` and `derivation_path`, using
+    /// `passphrase` as the BIP-39 "25th word" when generating the seed. This is needed to
+    /// reproduce the addresses of a mnemonic that was created with a passphrase elsewhere (e.g.
+    /// on a hardware wallet); [MnemonicWallet::new] is equivalent to calling this with an empty
+    /// passphrase.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Err`] if the provided `mnemonic_phrase` or `derivation_path` is invalid.
+    pub fn new_with_passphrase(
+        mnemonic_phrase: &str,
+        passphrase: &str,
+        derivation_path: &str,
     ) -> Result<MnemonicWallet, WalletError> {
         // Create mnemonic and generate seed from it
         let mnemonic = Mnemonic::from_phrase(mnemonic_phrase, Language::English)
             .map_err(|err| WalletError::Mnemonic(err.to_string()))?;
 
-        let seed = Seed::new(&mnemonic, "");
+        let seed = Seed::new(&mnemonic, passphrase);
 
         // Set hd_path for master_key generation
         let hd_path = StandardHDPath::try_from(derivation_path)
@@ -67,12 +304,85 @@ impl MnemonicWallet {
         let keychain = MnemonicWallet::generate_keychain(hd_path, seed)?;
 
         Ok(MnemonicWallet {
-            mnemonic,
+            mnemonic: Some(mnemonic),
+            passphrase: passphrase.to_owned(),
             keychain,
             derivation_path: derivation_path.to_owned(),
         })
     }
 
+    /// Derive a Secp256k1 key pair from the given `mnemonic_phrase` using a structured [Bip44]
+    /// path instead of a hand-assembled string, folding `passphrase` (the BIP-39 "25th word")
+    /// into the seed. Equivalent to [MnemonicWallet::new_with_passphrase] with
+    /// `bip44.to_string()` as the derivation path, which lets callers iterate `account`/
+    /// `address_index` programmatically (e.g. for [MnemonicWallet::generate_vanity] or
+    /// multi-account discovery) without building and re-parsing path strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Err`] if the provided `mnemonic_phrase` is invalid.
+    pub fn new_bip44(
+        mnemonic_phrase: &str,
+        passphrase: &str,
+        bip44: &Bip44,
+    ) -> Result<MnemonicWallet, WalletError> {
+        MnemonicWallet::new_with_passphrase(mnemonic_phrase, passphrase, &bip44.to_string())
+    }
+
+    /// Derives a [MnemonicWallet] from a [crate::seed::Seed] recovered (or generated) by
+    /// [crate::seed::Seed::from_file_or_generate], applying `derivation_path` to it. Equivalent to
+    /// [MnemonicWallet::new] with `seed.phrase()`, except the caller never has the mnemonic phrase
+    /// itself on hand.
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if `derivation_path` is invalid.
+    pub fn from_seed(
+        seed: &crate::seed::Seed,
+        derivation_path: &str,
+    ) -> Result<MnemonicWallet, WalletError> {
+        MnemonicWallet::new(seed.phrase(), derivation_path)
+    }
+
+    /// Builds a [MnemonicWallet] directly from a raw 32-byte secp256k1 private key, with no
+    /// mnemonic of its own. Used to import keys that didn't originate from a BIP-39 phrase (see
+    /// [MnemonicWallet::from_pem] and [MnemonicWallet::from_keystore]'s raw-key case).
+    /// `derivation_path` is kept only as a label describing how the key was derived elsewhere:
+    /// since there's no mnemonic to re-derive from, [MnemonicWallet::set_derivation_path] isn't
+    /// available on the result.
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if `private_key_bytes` isn't a valid secp256k1 scalar.
+    fn from_raw_private_key(
+        private_key_bytes: &[u8],
+        derivation_path: &str,
+    ) -> Result<MnemonicWallet, WalletError> {
+        let private_key = PrivateKey::from_slice(private_key_bytes, Network::Bitcoin)
+            .map_err(|err| WalletError::PrivateKey(err.to_string()))?;
+
+        // This key didn't come from BIP-32 derivation, so it has no real chain code or place in
+        // a hierarchy; the fields below are placeholders; only `private_key`/`public_key` matter
+        // to the rest of [MnemonicWallet].
+        let ext_private_key = ExtendedPrivKey {
+            network: Network::Bitcoin,
+            depth: 0,
+            parent_fingerprint: Fingerprint::from([0u8; 4]),
+            child_number: ChildNumber::Normal { index: 0 },
+            private_key,
+            chain_code: ChainCode::from([0u8; 32]),
+        };
+        let ext_public_key = ExtendedPubKey::from_private(&Secp256k1::new(), &ext_private_key);
+
+        Ok(MnemonicWallet {
+            mnemonic: None,
+            passphrase: String::new(),
+            derivation_path: derivation_path.to_owned(),
+            keychain: Keychain {
+                ext_private_key,
+                ext_public_key,
+            },
+        })
+    }
+
     /// Generates a random mnemonic phrase and derive a Secp256k1 key pair from it.
     ///
     /// # Errors
@@ -100,14 +410,17 @@ impl MnemonicWallet {
     ///
     /// # Errors
     ///
-    /// Returns an [`Err`] if the provided `derivation_path` is invalid.
+    /// Returns an [`Err`] if the provided `derivation_path` is invalid, or
+    /// [WalletError::NoMnemonic] if this wallet was imported directly from key material and has
+    /// no mnemonic to re-derive from.
     pub fn set_derivation_path(&mut self, derivation_path: &str) -> Result<(), WalletError> {
         // Update only if the derivation path is different.
         if derivation_path == self.derivation_path {
             return Ok(());
         }
 
-        let seed = Seed::new(&self.mnemonic, "");
+        let mnemonic = self.mnemonic.as_ref().ok_or(WalletError::NoMnemonic)?;
+        let seed = Seed::new(mnemonic, &self.passphrase);
 
         // Set hd_path for master_key generation
         let hd_path = StandardHDPath::try_from(derivation_path)
@@ -144,6 +457,11 @@ impl MnemonicWallet {
         self.keychain.ext_public_key.public_key
     }
 
+    /// Gets the derivation path this wallet's key pair was derived with.
+    pub fn get_derivation_path(&self) -> &str {
+        &self.derivation_path
+    }
+
     /// Gets the bech32 address derived from the mnemonic and the provided
     /// human readable part.
     ///
@@ -153,24 +471,181 @@ impl MnemonicWallet {
     /// * If the hrp contains any non-ASCII characters (outside 33..=126).
     /// * If the hrp is outside 1..83 characters long.
     pub fn get_bech32_address(&self, hrp: &str) -> Result<String, WalletError> {
-        let mut hasher = Sha256::new();
-        let pub_key_bytes = self.get_pub_key().to_bytes();
-        hasher.update(pub_key_bytes);
+        bech32_address_from_pub_key(&self.get_pub_key(), hrp)
+    }
+
+    /// Derives a range of BIP-44 accounts from this wallet's mnemonic without disturbing its own
+    /// keychain or derivation path: `base_path` is a BIP-44 path missing its final index (e.g.
+    /// `m/44'/118'/0'/0`), which is varied over `account_range` to produce one derived public
+    /// key and bech32 address per index. Useful for account-discovery flows where a UI needs to
+    /// show several addresses (and their balances) recovered from a single seed.
+    ///
+    /// # Errors
+    /// Returns [WalletError::NoMnemonic] if this wallet was imported directly from key material
+    /// and has no mnemonic to derive from, [WalletError::DerivationPath] if `base_path` combined
+    /// with an index from `account_range` isn't a valid derivation path, and the usual
+    /// [MnemonicWallet::get_bech32_address] errors.
+    pub fn derive_accounts(
+        &self,
+        base_path: &str,
+        account_range: Range<u32>,
+        hrp: &str,
+    ) -> Result<Vec<(String, PublicKey, String)>, WalletError> {
+        let mnemonic = self.mnemonic.as_ref().ok_or(WalletError::NoMnemonic)?;
+
+        let mut accounts = Vec::with_capacity(account_range.len());
+        for index in account_range {
+            let path = format!("{base_path}/{index}");
+            let hd_path = StandardHDPath::try_from(path.as_str())
+                .map_err(|_| WalletError::DerivationPath(path.clone()))?;
 
-        // Read hash digest over the public key bytes & consume hasher
-        let pk_hash = hasher.finalize();
+            let seed = Seed::new(mnemonic, &self.passphrase);
+            let keychain = MnemonicWallet::generate_keychain(hd_path, seed)?;
+            let pub_key = keychain.ext_public_key.public_key;
+            let address = bech32_address_from_pub_key(&pub_key, hrp)?;
 
-        // Insert the hash result in the ripdem hash function
-        let mut rip_hasher = Ripemd160::new();
-        rip_hasher.update(pk_hash);
-        let rip_result = rip_hasher.finalize();
+            accounts.push((path, pub_key, address));
+        }
 
-        let address_bytes = rip_result.to_vec();
+        Ok(accounts)
+    }
 
-        let bech32_address = bech32::encode(hrp, address_bytes.to_base32(), Bech32)
-            .map_err(|err| WalletError::Hrp(err.to_string()))?;
+    /// Derives the account at `base_path`/`index` and computes its bech32 address, the per-index
+    /// unit of work of [MnemonicWallet::generate_vanity] ([MnemonicWallet::derive_accounts] does
+    /// the same derivation inline).
+    fn derive_vanity_candidate(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        base_path: &str,
+        hrp: &str,
+        index: u32,
+    ) -> Result<(String, PublicKey, String), WalletError> {
+        let path = format!("{base_path}/{index}");
+        let hd_path = StandardHDPath::try_from(path.as_str())
+            .map_err(|_| WalletError::DerivationPath(path.clone()))?;
 
-        Ok(bech32_address)
+        let seed = Seed::new(mnemonic, passphrase);
+        let keychain = MnemonicWallet::generate_keychain(hd_path, seed)?;
+        let pub_key = keychain.ext_public_key.public_key;
+        let address = bech32_address_from_pub_key(&pub_key, hrp)?;
+
+        Ok((path, pub_key, address))
+    }
+
+    /// Searches the BIP-44 address-index space under `base_path` (e.g. `m/44'/118'/0'/0`) for an
+    /// account whose bech32 address under `hrp` matches `pattern`, incrementing the final index
+    /// `i` for `i = 0, 1, 2, ...` up to `max_attempts` derivations. The winning index is returned
+    /// alongside the match, so the same address can be reproduced later from this wallet's
+    /// mnemonic (e.g. via [MnemonicWallet::derive_accounts]) instead of storing a new key.
+    ///
+    /// On native targets the search is spread across [`std::thread::available_parallelism`]
+    /// worker threads, each claiming the next unsearched index until one of them finds a match or
+    /// `max_attempts` is exhausted; wasm has no threads to spread it across, so it runs on the
+    /// calling one.
+    ///
+    /// # Errors
+    /// Returns [WalletError::NoMnemonic] if this wallet was imported directly from key material
+    /// and has no mnemonic to derive from, or [WalletError::Vanity] if no match turns up within
+    /// `max_attempts`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn generate_vanity(
+        &self,
+        base_path: &str,
+        hrp: &str,
+        pattern: &VanityPattern,
+        max_attempts: u32,
+    ) -> Result<(String, PublicKey, String), WalletError> {
+        let mnemonic = self.mnemonic.as_ref().ok_or(WalletError::NoMnemonic)?;
+
+        let thread_count = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        let next_index = Arc::new(AtomicU32::new(0));
+        let found: Arc<Mutex<Option<(String, PublicKey, String)>>> = Arc::new(Mutex::new(None));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handles: Vec<_> = (0..thread_count)
+            .map(|_| {
+                let mnemonic = mnemonic.clone();
+                let passphrase = self.passphrase.clone();
+                let base_path = base_path.to_owned();
+                let hrp = hrp.to_owned();
+                let pattern = pattern.clone();
+                let next_index = Arc::clone(&next_index);
+                let found = Arc::clone(&found);
+                let stop = Arc::clone(&stop);
+
+                thread::spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        let index = next_index.fetch_add(1, Ordering::Relaxed);
+                        if index >= max_attempts {
+                            return;
+                        }
+
+                        let candidate = MnemonicWallet::derive_vanity_candidate(
+                            &mnemonic,
+                            &passphrase,
+                            &base_path,
+                            &hrp,
+                            index,
+                        );
+                        let Ok((path, pub_key, address)) = candidate else {
+                            continue;
+                        };
+
+                        let data_part = address.split_once('1').map_or("", |(_, data)| data);
+                        if pattern.matches(data_part) {
+                            *found.lock().unwrap() = Some((path, pub_key, address));
+                            stop.store(true, Ordering::Relaxed);
+                            return;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let _ = handle.join();
+        }
+
+        found.lock().unwrap().take().ok_or_else(|| {
+            WalletError::Vanity(format!(
+                "no address matching the given pattern found within {max_attempts} attempts"
+            ))
+        })
+    }
+
+    /// See the native implementation's documentation above; wasm has no threads to spread the
+    /// search across, so it runs sequentially on the calling one.
+    #[cfg(target_arch = "wasm32")]
+    pub fn generate_vanity(
+        &self,
+        base_path: &str,
+        hrp: &str,
+        pattern: &VanityPattern,
+        max_attempts: u32,
+    ) -> Result<(String, PublicKey, String), WalletError> {
+        let mnemonic = self.mnemonic.as_ref().ok_or(WalletError::NoMnemonic)?;
+
+        for index in 0..max_attempts {
+            let (path, pub_key, address) = MnemonicWallet::derive_vanity_candidate(
+                mnemonic,
+                &self.passphrase,
+                base_path,
+                hrp,
+                index,
+            )?;
+
+            let data_part = address.split_once('1').map_or("", |(_, data)| data);
+            if pattern.matches(data_part) {
+                return Ok((path, pub_key, address));
+            }
+        }
+
+        Err(WalletError::Vanity(format!(
+            "no address matching the given pattern found within {max_attempts} attempts"
+        )))
     }
 
     /// Returns the signature of the provided data.
@@ -189,6 +664,234 @@ impl MnemonicWallet {
 
         Ok(signature.as_ref().to_vec())
     }
+
+    /// Verifies that `sig` is a valid Secp256k1 ECDSA signature over `msg` produced by the holder
+    /// of `pub_key` (SEC1-encoded, compressed or uncompressed). Mirrors OpenEthereum's
+    /// `ethkey::verify_public`.
+    ///
+    /// # Errors
+    /// Returns [WalletError::Verify] if `pub_key` or `sig` aren't validly encoded.
+    pub fn verify(msg: &[u8], sig: &[u8], pub_key: &[u8]) -> Result<bool, WalletError> {
+        let verifying_key =
+            VerifyingKey::from_sec1_bytes(pub_key).map_err(|err| WalletError::Verify(err.to_string()))?;
+        let signature =
+            Signature::try_from(sig).map_err(|err| WalletError::Verify(err.to_string()))?;
+
+        Ok(verifying_key.verify(msg, &signature).is_ok())
+    }
+
+    /// Encrypts this wallet's secret (its mnemonic phrase, or the raw private key if it has none)
+    /// with `password` and serializes it as a Web3 Secret Storage ("UTC/JSON keystore") document,
+    /// the same format used by other Cosmos/Ethereum SDKs. This lets a consumer persist the
+    /// wallet to disk without keeping the plaintext secret around; restore it with
+    /// [MnemonicWallet::from_keystore].
+    ///
+    /// # Errors
+    /// Returns [WalletError::Keystore] if the underlying scrypt/AES primitives fail or the
+    /// document can't be serialized.
+    pub fn to_keystore(&self, password: &str) -> Result<String, WalletError> {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        let mut iv = [0u8; 16];
+        OsRng.fill_bytes(&mut iv);
+
+        let derived_key = derive_scrypt_key(
+            password,
+            &salt,
+            KEYSTORE_SCRYPT_LOG_N,
+            KEYSTORE_SCRYPT_R,
+            KEYSTORE_SCRYPT_P,
+        )?;
+
+        let mut ciphertext = match &self.mnemonic {
+            Some(mnemonic) => {
+                let mut plaintext = vec![KEYSTORE_SECRET_MNEMONIC];
+                plaintext.extend_from_slice(mnemonic.phrase().as_bytes());
+                plaintext
+            }
+            None => {
+                let mut plaintext = vec![KEYSTORE_SECRET_RAW_KEY];
+                plaintext.extend_from_slice(&self.keychain.ext_private_key.private_key.to_bytes());
+                plaintext
+            }
+        };
+        apply_aes128_ctr(&derived_key[..16], &iv, &mut ciphertext);
+
+        let mac = keccak256(&[&derived_key[16..32], ciphertext.as_slice()].concat());
+
+        let keystore = Keystore {
+            version: KEYSTORE_VERSION,
+            id: Uuid::new_v4().to_string(),
+            crypto: KeystoreCrypto {
+                cipher: "aes-128-ctr".to_owned(),
+                ciphertext: hex::encode(&ciphertext),
+                cipherparams: KeystoreCipherParams {
+                    iv: hex::encode(iv),
+                },
+                kdf: "scrypt".to_owned(),
+                kdfparams: KeystoreKdfParams {
+                    dklen: KEYSTORE_DKLEN as u8,
+                    n: 1u32 << KEYSTORE_SCRYPT_LOG_N,
+                    r: KEYSTORE_SCRYPT_R,
+                    p: KEYSTORE_SCRYPT_P,
+                    salt: hex::encode(salt),
+                },
+                mac: hex::encode(mac),
+            },
+        };
+
+        serde_json::to_string(&keystore).map_err(|err| WalletError::Keystore(err.to_string()))
+    }
+
+    /// Decrypts a keystore produced by [MnemonicWallet::to_keystore] with `password` and
+    /// restores the wallet it was exported from, re-deriving it at `derivation_path` if the
+    /// keystore holds a mnemonic (not itself stored in the keystore), or importing the raw key
+    /// directly if it doesn't.
+    ///
+    /// # Errors
+    /// Returns [WalletError::Keystore] if `json` is malformed, the MAC doesn't match (wrong
+    /// password or a corrupted file), or the secret tag byte is unrecognized, and the usual
+    /// [MnemonicWallet::new]/[MnemonicWallet::from_raw_private_key] errors if the decrypted
+    /// secret or `derivation_path` turn out to be invalid.
+    pub fn from_keystore(
+        json: &str,
+        password: &str,
+        derivation_path: &str,
+    ) -> Result<MnemonicWallet, WalletError> {
+        let keystore: Keystore =
+            serde_json::from_str(json).map_err(|err| WalletError::Keystore(err.to_string()))?;
+
+        let salt = hex::decode(&keystore.crypto.kdfparams.salt)
+            .map_err(|err| WalletError::Keystore(err.to_string()))?;
+        let log_n = keystore.crypto.kdfparams.n.trailing_zeros() as u8;
+
+        let derived_key = derive_scrypt_key(
+            password,
+            &salt,
+            log_n,
+            keystore.crypto.kdfparams.r,
+            keystore.crypto.kdfparams.p,
+        )?;
+
+        let mut ciphertext = hex::decode(&keystore.crypto.ciphertext)
+            .map_err(|err| WalletError::Keystore(err.to_string()))?;
+
+        let expected_mac = keccak256(&[&derived_key[16..32], ciphertext.as_slice()].concat());
+        let mac = hex::decode(&keystore.crypto.mac)
+            .map_err(|err| WalletError::Keystore(err.to_string()))?;
+        if expected_mac.as_slice() != mac.as_slice() {
+            return Err(WalletError::Keystore(
+                "MAC mismatch: wrong password or corrupted keystore".to_owned(),
+            ));
+        }
+
+        let iv = hex::decode(&keystore.crypto.cipherparams.iv)
+            .map_err(|err| WalletError::Keystore(err.to_string()))?;
+        apply_aes128_ctr(&derived_key[..16], &iv, &mut ciphertext);
+
+        if ciphertext.is_empty() {
+            return Err(WalletError::Keystore(
+                "decrypted keystore payload is empty".to_owned(),
+            ));
+        }
+        let (tag, secret) = ciphertext.split_at(1);
+        match tag[0] {
+            KEYSTORE_SECRET_MNEMONIC => {
+                let mnemonic_phrase = String::from_utf8(secret.to_vec())
+                    .map_err(|err| WalletError::Keystore(err.to_string()))?;
+                MnemonicWallet::new(&mnemonic_phrase, derivation_path)
+            }
+            KEYSTORE_SECRET_RAW_KEY => MnemonicWallet::from_raw_private_key(secret, derivation_path),
+            other => Err(WalletError::Keystore(format!(
+                "unrecognized keystore secret tag {other}"
+            ))),
+        }
+    }
+
+    /// Serializes this wallet's private key material to a PEM block: the bech32 address for
+    /// `hrp`, followed by the 32-byte private key and the 32-byte x-coordinate of the public key,
+    /// matching the unencrypted key file layout used by several Cosmos-ecosystem CLIs.
+    ///
+    /// # Errors
+    /// Returns an [`Err`] if `hrp` is not a valid bech32 human readable part (see
+    /// [MnemonicWallet::get_bech32_address]).
+    pub fn to_pem(&self, hrp: &str) -> Result<String, WalletError> {
+        let address = self.get_bech32_address(hrp)?;
+        let address_bytes = address.as_bytes();
+
+        let mut contents = Vec::with_capacity(1 + address_bytes.len() + 32 + 32);
+        contents.push(address_bytes.len() as u8);
+        contents.extend_from_slice(address_bytes);
+        contents.extend_from_slice(&self.keychain.ext_private_key.private_key.to_bytes());
+
+        // `to_bytes()` is compressed (a 1-byte sign prefix followed by the 32-byte x-coordinate);
+        // drop the prefix to keep the PEM layout a fixed 32 bytes per key. The bech32 address, by
+        // contrast, is hashed from all 33 bytes.
+        let pub_key_bytes = self.get_pub_key().to_bytes();
+        contents.extend_from_slice(&pub_key_bytes[1..33]);
+
+        Ok(pem::encode(&Pem {
+            tag: PEM_TAG.to_owned(),
+            contents,
+        }))
+    }
+
+    /// Restores a wallet from a PEM block produced by [MnemonicWallet::to_pem], importing the
+    /// private key directly (the bech32 address and public key in the block are not re-checked,
+    /// they're there for readability). The restored wallet has no mnemonic of its own; see
+    /// [MnemonicWallet::from_raw_private_key].
+    ///
+    /// # Errors
+    /// Returns [WalletError::Pem] if `pem` isn't a well-formed PEM block or its contents don't
+    /// match the layout produced by [MnemonicWallet::to_pem], and [WalletError::PrivateKey] if
+    /// the embedded private key is invalid.
+    pub fn from_pem(pem: &str, derivation_path: &str) -> Result<MnemonicWallet, WalletError> {
+        let parsed = pem::parse(pem).map_err(|err| WalletError::Pem(err.to_string()))?;
+        let contents = parsed.contents;
+
+        let address_len = *contents
+            .first()
+            .ok_or_else(|| WalletError::Pem("empty PEM contents".to_owned()))?
+            as usize;
+
+        if contents.len() != 1 + address_len + 32 + 32 {
+            return Err(WalletError::Pem(
+                "PEM contents don't match the expected address/private-key/public-key layout"
+                    .to_owned(),
+            ));
+        }
+
+        let private_key_bytes = &contents[1 + address_len..1 + address_len + 32];
+        MnemonicWallet::from_raw_private_key(private_key_bytes, derivation_path)
+    }
+}
+
+/// Converts a PEM key file (see [MnemonicWallet::to_pem]) into an encrypted Web3 Secret Storage
+/// keystore (see [MnemonicWallet::to_keystore]), so operators can migrate a key file to the
+/// encrypted format offline without re-entering the mnemonic.
+///
+/// # Errors
+/// Returns the usual [MnemonicWallet::from_pem]/[MnemonicWallet::to_keystore] errors.
+pub fn convert_pem_to_keystore(
+    pem: &str,
+    derivation_path: &str,
+    password: &str,
+) -> Result<String, WalletError> {
+    MnemonicWallet::from_pem(pem, derivation_path)?.to_keystore(password)
+}
+
+/// Converts an encrypted Web3 Secret Storage keystore (see [MnemonicWallet::to_keystore]) into a
+/// PEM key file (see [MnemonicWallet::to_pem]), the reverse of [convert_pem_to_keystore].
+///
+/// # Errors
+/// Returns the usual [MnemonicWallet::from_keystore]/[MnemonicWallet::to_pem] errors.
+pub fn convert_keystore_to_pem(
+    json: &str,
+    password: &str,
+    derivation_path: &str,
+    hrp: &str,
+) -> Result<String, WalletError> {
+    MnemonicWallet::from_keystore(json, password, derivation_path)?.to_pem(hrp)
 }
 
 #[cfg(test)]
@@ -207,6 +910,43 @@ mod tests {
         assert!(result.is_ok())
     }
 
+    #[test]
+    fn bip44_default_is_the_cosmos_hub_path() {
+        assert_eq!(Bip44::default().to_string(), COSMOS_DERIVATION_PATH);
+    }
+
+    #[test]
+    fn bip44_displays_as_the_manually_assembled_path() {
+        let bip44 = Bip44 {
+            coin_type: 852,
+            account: 0,
+            change: 0,
+            address_index: 0,
+        };
+
+        assert_eq!(bip44.to_string(), DESMOS_DERIVATION_PATH);
+    }
+
+    #[test]
+    fn new_bip44_matches_the_manually_assembled_path() {
+        let manual = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let structured = MnemonicWallet::new_bip44(
+            TEST_MNEMONIC,
+            "",
+            &Bip44 {
+                coin_type: 852,
+                ..Bip44::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            manual.get_bech32_address("desmos").unwrap(),
+            structured.get_bech32_address("desmos").unwrap()
+        );
+    }
+
     #[test]
     fn initialize_with_invalid_mnemonic() {
         let result = MnemonicWallet::new("an invalid mnemonic", DESMOS_DERIVATION_PATH);
@@ -280,6 +1020,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn passphrase_changes_derived_address() {
+        let without_passphrase =
+            MnemonicWallet::new(TEST_MNEMONIC, COSMOS_DERIVATION_PATH).unwrap();
+        let with_passphrase = MnemonicWallet::new_with_passphrase(
+            TEST_MNEMONIC,
+            "25th word",
+            COSMOS_DERIVATION_PATH,
+        )
+        .unwrap();
+
+        assert_ne!(
+            without_passphrase.get_bech32_address("cosmos").unwrap(),
+            with_passphrase.get_bech32_address("cosmos").unwrap()
+        );
+    }
+
+    #[test]
+    fn set_derivation_path_keeps_passphrase() {
+        let mut wallet = MnemonicWallet::new_with_passphrase(
+            TEST_MNEMONIC,
+            "25th word",
+            COSMOS_DERIVATION_PATH,
+        )
+        .unwrap();
+        let reference = MnemonicWallet::new_with_passphrase(
+            TEST_MNEMONIC,
+            "25th word",
+            DESMOS_DERIVATION_PATH,
+        )
+        .unwrap();
+
+        wallet.set_derivation_path(DESMOS_DERIVATION_PATH).unwrap();
+
+        assert_eq!(
+            reference.get_bech32_address("desmos").unwrap(),
+            wallet.get_bech32_address("desmos").unwrap()
+        );
+    }
+
     #[test]
     fn empty_sign() {
         let wallet = MnemonicWallet::new(TEST_MNEMONIC, COSMOS_DERIVATION_PATH).unwrap();
@@ -303,4 +1083,244 @@ mod tests {
 
         assert_eq!(ref_hex_signature, sign_hex);
     }
+
+    #[test]
+    fn verify_accepts_a_valid_signature_and_rejects_a_tampered_one() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let data = "some simple data".as_bytes();
+        let signature = wallet.sign(data).unwrap();
+        let pub_key = wallet.get_pub_key().to_bytes();
+
+        assert!(MnemonicWallet::verify(data, &signature, &pub_key).unwrap());
+        assert!(!MnemonicWallet::verify("other data".as_bytes(), &signature, &pub_key).unwrap());
+    }
+
+    #[test]
+    fn verify_rejects_a_malformed_public_key() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let data = "some simple data".as_bytes();
+        let signature = wallet.sign(data).unwrap();
+
+        let result = MnemonicWallet::verify(data, &signature, &[0u8; 4]);
+
+        assert!(matches!(result, Err(WalletError::Verify(_))));
+    }
+
+    #[test]
+    fn recover_address_finds_the_signer() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let data = "some simple data".as_bytes();
+
+        let sign_key =
+            SigningKey::from_bytes(&wallet.keychain.ext_private_key.private_key.to_bytes())
+                .unwrap();
+        let signature: recoverable::Signature = sign_key.try_sign(data).unwrap();
+
+        let address = recover_address(data, signature.as_ref(), "desmos").unwrap();
+
+        assert_eq!(wallet.get_bech32_address("desmos").unwrap(), address);
+    }
+
+    #[test]
+    fn keystore_round_trip() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let keystore = wallet.to_keystore("super secret password").unwrap();
+        let restored =
+            MnemonicWallet::from_keystore(&keystore, "super secret password", DESMOS_DERIVATION_PATH)
+                .unwrap();
+
+        assert_eq!(
+            wallet.get_bech32_address("desmos").unwrap(),
+            restored.get_bech32_address("desmos").unwrap()
+        );
+    }
+
+    #[test]
+    fn keystore_wrong_password_fails() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let keystore = wallet.to_keystore("super secret password").unwrap();
+
+        let result =
+            MnemonicWallet::from_keystore(&keystore, "wrong password", DESMOS_DERIVATION_PATH);
+
+        assert!(matches!(result, Err(WalletError::Keystore(_))));
+    }
+
+    #[test]
+    fn pem_round_trip() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+
+        let pem = wallet.to_pem("desmos").unwrap();
+        let restored = MnemonicWallet::from_pem(&pem, DESMOS_DERIVATION_PATH).unwrap();
+
+        assert_eq!(
+            wallet.get_bech32_address("desmos").unwrap(),
+            restored.get_bech32_address("desmos").unwrap()
+        );
+    }
+
+    #[test]
+    fn pem_restored_wallet_has_no_mnemonic() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let pem = wallet.to_pem("desmos").unwrap();
+        let mut restored = MnemonicWallet::from_pem(&pem, DESMOS_DERIVATION_PATH).unwrap();
+
+        let result = restored.set_derivation_path(COSMOS_DERIVATION_PATH);
+
+        assert!(matches!(result, Err(WalletError::NoMnemonic)));
+    }
+
+    #[test]
+    fn from_pem_rejects_malformed_contents() {
+        let pem = pem::encode(&pem::Pem {
+            tag: "COSMOS PRIVATE KEY".to_owned(),
+            contents: vec![1, 2, 3],
+        });
+
+        let result = MnemonicWallet::from_pem(&pem, DESMOS_DERIVATION_PATH);
+
+        assert!(matches!(result, Err(WalletError::Pem(_))));
+    }
+
+    #[test]
+    fn convert_pem_to_keystore_and_back() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let pem = wallet.to_pem("desmos").unwrap();
+
+        let keystore =
+            convert_pem_to_keystore(&pem, DESMOS_DERIVATION_PATH, "super secret password")
+                .unwrap();
+        let roundtripped_pem = convert_keystore_to_pem(
+            &keystore,
+            "super secret password",
+            DESMOS_DERIVATION_PATH,
+            "desmos",
+        )
+        .unwrap();
+        let restored = MnemonicWallet::from_pem(&roundtripped_pem, DESMOS_DERIVATION_PATH).unwrap();
+
+        assert_eq!(
+            wallet.get_bech32_address("desmos").unwrap(),
+            restored.get_bech32_address("desmos").unwrap()
+        );
+    }
+
+    #[test]
+    fn derive_accounts_matches_single_account_wallet() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, COSMOS_DERIVATION_PATH).unwrap();
+
+        let accounts = wallet
+            .derive_accounts("m/44'/118'/0'/0", 0..3, "cosmos")
+            .unwrap();
+
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[0].0, "m/44'/118'/0'/0/0");
+        assert_eq!(accounts[0].2, wallet.get_bech32_address("cosmos").unwrap());
+
+        let other = MnemonicWallet::new(TEST_MNEMONIC, "m/44'/118'/0'/0/2").unwrap();
+        assert_eq!(accounts[2].1, other.get_pub_key());
+        assert_eq!(accounts[2].2, other.get_bech32_address("cosmos").unwrap());
+    }
+
+    #[test]
+    fn derive_accounts_does_not_disturb_wallet_keychain() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, COSMOS_DERIVATION_PATH).unwrap();
+
+        wallet
+            .derive_accounts("m/44'/118'/0'/0", 0..5, "cosmos")
+            .unwrap();
+
+        assert_eq!(wallet.get_derivation_path(), COSMOS_DERIVATION_PATH);
+        assert_eq!(
+            wallet.get_bech32_address("cosmos").unwrap(),
+            "cosmos1dzczdka6wpzwvmawpps7tf8047gkft0e5cupun"
+        );
+    }
+
+    #[test]
+    fn derive_accounts_rejects_invalid_base_path() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, COSMOS_DERIVATION_PATH).unwrap();
+
+        let result = wallet.derive_accounts("not a path", 0..1, "cosmos");
+
+        assert!(matches!(result, Err(WalletError::DerivationPath(_))));
+    }
+
+    #[test]
+    fn derive_accounts_requires_mnemonic() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let pem = wallet.to_pem("desmos").unwrap();
+        let restored = MnemonicWallet::from_pem(&pem, DESMOS_DERIVATION_PATH).unwrap();
+
+        let result = restored.derive_accounts("m/44'/852'/0'/0", 0..1, "desmos");
+
+        assert!(matches!(result, Err(WalletError::NoMnemonic)));
+    }
+
+    #[test]
+    fn generate_vanity_finds_a_prefix_match() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, COSMOS_DERIVATION_PATH).unwrap();
+
+        // Index 0 is already known (see derive_accounts_does_not_disturb_wallet_keychain) to
+        // produce "cosmos1dzczdka...", so a prefix match on its data part should win immediately.
+        let (path, pub_key, address) = wallet
+            .generate_vanity(
+                "m/44'/118'/0'/0",
+                "cosmos",
+                &VanityPattern::Prefix("dzczd".to_string()),
+                1,
+            )
+            .unwrap();
+
+        assert_eq!(path, "m/44'/118'/0'/0/0");
+        assert_eq!(pub_key, wallet.get_pub_key());
+        assert_eq!(address, wallet.get_bech32_address("cosmos").unwrap());
+    }
+
+    #[test]
+    fn generate_vanity_fails_when_attempts_are_exhausted() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, COSMOS_DERIVATION_PATH).unwrap();
+
+        let result = wallet.generate_vanity(
+            "m/44'/118'/0'/0",
+            "cosmos",
+            &VanityPattern::Prefix("impossiblylongprefixnoaddresswillever".to_string()),
+            2,
+        );
+
+        assert!(matches!(result, Err(WalletError::Vanity(_))));
+    }
+
+    #[test]
+    fn generate_vanity_requires_mnemonic() {
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DESMOS_DERIVATION_PATH).unwrap();
+        let pem = wallet.to_pem("desmos").unwrap();
+        let restored = MnemonicWallet::from_pem(&pem, DESMOS_DERIVATION_PATH).unwrap();
+
+        let result = restored.generate_vanity(
+            "m/44'/852'/0'/0",
+            "desmos",
+            &VanityPattern::Prefix("a".to_string()),
+            1,
+        );
+
+        assert!(matches!(result, Err(WalletError::NoMnemonic)));
+    }
+
+    #[test]
+    fn vanity_pattern_charset_rejects_characters_outside_the_set() {
+        let pattern = VanityPattern::Charset("abc".to_string());
+
+        assert!(pattern.matches("aabbcc"));
+        assert!(!pattern.matches("aabbccd"));
+    }
+
+    #[test]
+    fn vanity_pattern_suffix_matches_trailing_characters() {
+        let pattern = VanityPattern::Suffix("xyz".to_string());
+
+        assert!(pattern.matches("somethingxyz"));
+        assert!(!pattern.matches("somethingelse"));
+    }
 }
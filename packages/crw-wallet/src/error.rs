@@ -17,4 +17,22 @@ pub enum WalletError {
 
     #[error("invalid human readable path {0}")]
     Hrp(String),
+
+    #[error("vanity address error: {0}")]
+    Vanity(String),
+
+    #[error("signature verification error: {0}")]
+    Verify(String),
+
+    #[error("keystore error: {0}")]
+    Keystore(String),
+
+    #[error("pem error: {0}")]
+    Pem(String),
+
+    #[error("wallet has no mnemonic to re-derive from")]
+    NoMnemonic,
+
+    #[error("preferences error: {0}")]
+    Preferences(String),
 }
@@ -5,8 +5,19 @@ use bip39::{Language, Mnemonic, MnemonicType};
 use libc::{c_char, c_int, c_uchar, c_uint, size_t};
 use std::ffi::{CStr, CString};
 use std::ptr::null_mut;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use std::{mem, slice};
 
+/// Bech32 data-part alphabet. Notably excludes `1`, `b`, `i` and `o` to avoid visual ambiguity.
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Derivation path used to search for a vanity address, matching the one used throughout the
+/// rest of this crate's examples and tests.
+const VANITY_DERIVATION_PATH: &str = "m/44'/118'/0'/0/0";
+
 #[repr(C)]
 pub struct Signature {
     len: c_uint,
@@ -230,14 +241,118 @@ pub extern "C" fn wallet_sign_free(ptr: *mut Signature) {
     Box::from(ptr);
 }
 
+/// Generates a random 24-word mnemonic whose bech32 address, derived at
+/// `m/44'/118'/0'/0/0`, starts with `prefix` right after the `hrp1` separator.
+///
+/// Spawns `threads` worker threads (at least 1) that each repeatedly generate a random
+/// mnemonic, derive its address and compare it against `prefix`, stopping as soon as any
+/// thread finds a match or `timeout_ms` milliseconds elapse. Every extra character in `prefix`
+/// multiplies the expected number of attempts (and thus the expected run time) by roughly 32,
+/// since the bech32 alphabet has 32 symbols.
+///
+/// The returned mnemonic must be freed using the [`cstring_free`] function to avoid memory leaks.
+///
+/// # Errors
+/// This function returns a nullptr if `prefix` contains characters outside the bech32 alphabet
+/// (`1`, `b`, `i` and `o` are excluded) or if no match is found within `timeout_ms`. The error
+/// cause can be accessed using the [error_message_utf8](ffi_helpers::error_handling::error_message_utf8) function.
+#[no_mangle]
+pub extern "C" fn wallet_generate_vanity(
+    hrp: *const c_char,
+    prefix: *const c_char,
+    threads: c_uint,
+    timeout_ms: c_uint,
+) -> *mut c_char {
+    if hrp.is_null() {
+        ffi_helpers::update_last_error(WalletError::Hrp("received null hrp".to_owned()));
+        return null_mut();
+    }
+    if prefix.is_null() {
+        ffi_helpers::update_last_error(WalletError::Vanity("received null prefix".to_owned()));
+        return null_mut();
+    }
+
+    let hrp = unsafe { CStr::from_ptr(hrp).to_string_lossy().to_string() };
+    let prefix = unsafe { CStr::from_ptr(prefix).to_string_lossy().to_string() };
+
+    if let Some(invalid) = prefix.chars().find(|c| !BECH32_CHARSET.contains(*c)) {
+        ffi_helpers::update_last_error(WalletError::Vanity(format!(
+            "'{}' is not part of the bech32 charset",
+            invalid
+        )));
+        return null_mut();
+    }
+
+    let thread_count = threads.max(1) as usize;
+    let timeout = Duration::from_millis(timeout_ms as u64);
+    let needle = format!("{}1{}", hrp, prefix);
+
+    let found: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+    let stop = Arc::new(AtomicBool::new(false));
+    let attempts = Arc::new(AtomicU64::new(0));
+    let start = Instant::now();
+
+    let handles: Vec<_> = (0..thread_count)
+        .map(|_| {
+            let hrp = hrp.clone();
+            let needle = needle.clone();
+            let found = Arc::clone(&found);
+            let stop = Arc::clone(&stop);
+            let attempts = Arc::clone(&attempts);
+
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) && start.elapsed() < timeout {
+                    let mnemonic = Mnemonic::new(MnemonicType::Words24, Language::English);
+                    let phrase = mnemonic.phrase().to_owned();
+                    attempts.fetch_add(1, Ordering::Relaxed);
+
+                    let address = MnemonicWallet::new(&phrase, VANITY_DERIVATION_PATH)
+                        .ok()
+                        .and_then(|wallet| wallet.get_bech32_address(&hrp).ok());
+
+                    if address.map_or(false, |addr| addr.starts_with(&needle)) {
+                        *found.lock().unwrap() = Some(phrase);
+                        stop.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    match found.lock().unwrap().take() {
+        Some(phrase) => match CString::new(phrase) {
+            Ok(s) => s.into_raw(),
+            Err(e) => {
+                ffi_helpers::update_last_error(e);
+                null_mut()
+            }
+        },
+        None => {
+            ffi_helpers::update_last_error(WalletError::Vanity(format!(
+                "no address matching prefix '{}' found within {}ms ({} attempts)",
+                prefix,
+                timeout_ms,
+                attempts.load(Ordering::Relaxed)
+            )));
+            null_mut()
+        }
+    }
+}
+
 // Macro to export the ffi_helpers's functions used to access the error message from other programming languages.
 export_error_handling_functions!();
 
 #[cfg(test)]
 mod tests {
     use crate::ffi::{
-        cstring_free, wallet_free, wallet_from_mnemonic, wallet_get_bech32_address,
-        wallet_get_public_key, wallet_random_mnemonic, wallet_sign, wallet_sign_free,
+        cstring_free, wallet_free, wallet_from_mnemonic, wallet_generate_vanity,
+        wallet_get_bech32_address, wallet_get_public_key, wallet_random_mnemonic, wallet_sign,
+        wallet_sign_free,
     };
     use ffi_helpers::error_handling::error_message;
     use std::ffi::CString;
@@ -489,4 +604,32 @@ mod tests {
         cstring_free(c_mnemonic);
         cstring_free(c_dp);
     }
+
+    #[test]
+    fn generate_vanity_rejects_invalid_prefix() {
+        let hrp = CString::new("cosmos").unwrap().into_raw();
+        // '1' is excluded from the bech32 charset.
+        let prefix = CString::new("1abc").unwrap().into_raw();
+
+        let mnemonic = wallet_generate_vanity(hrp, prefix, 1, 1000);
+        assert!(mnemonic.is_null());
+        assert!(error_message().is_some());
+
+        cstring_free(hrp);
+        cstring_free(prefix);
+    }
+
+    #[test]
+    fn generate_vanity_honors_timeout() {
+        let hrp = CString::new("cosmos").unwrap().into_raw();
+        // Long enough to be practically unreachable within the timeout.
+        let prefix = CString::new("qqqqqqqqqq").unwrap().into_raw();
+
+        let mnemonic = wallet_generate_vanity(hrp, prefix, 2, 50);
+        assert!(mnemonic.is_null());
+        assert!(error_message().is_some());
+
+        cstring_free(hrp);
+        cstring_free(prefix);
+    }
 }
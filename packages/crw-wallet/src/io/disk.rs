@@ -0,0 +1,265 @@
+//! Encrypted, file-locked disk storage for wallet material, via [save_encrypted]/[load_encrypted].
+//!
+//! Unlike [crate::keyring::Keyring], which persists through a pluggable
+//! [crw_preferences::preferences::Preferences] backend, this module writes directly to a single
+//! file under the user's config directory. Each file is a versioned envelope whose symmetric key
+//! is stretched from a passphrase with Argon2id and whose contents are sealed with
+//! XChaCha20-Poly1305, and every read/write takes an OS-level advisory lock so two wallet
+//! instances can't corrupt each other's writes.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use fd_lock::RwLock as FileLock;
+use once_cell::sync::Lazy;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Magic bytes marking a file written by [save_encrypted].
+const HEADER_MAGIC: &[u8; 4] = b"CWK1";
+/// Version of the `HEADER_MAGIC || HEADER_VERSION || salt || nonce || ciphertext` envelope
+/// currently written. [load_encrypted] rejects a file carrying any other version as
+/// [DiskError::InvalidFormat].
+const HEADER_VERSION: u8 = 1;
+/// Length in bytes of the random salt used to derive the encryption key.
+const SALT_LEN: usize = 16;
+/// Length in bytes of the random nonce used by XChaCha20-Poly1305.
+const NONCE_LEN: usize = 24;
+/// Argon2id memory cost, in KiB, used to stretch the passphrase on every [save_encrypted].
+const ARGON2_M_COST: u32 = 19456;
+/// Argon2id number of passes used to stretch the passphrase on every [save_encrypted].
+const ARGON2_T_COST: u32 = 2;
+/// Argon2id degree of parallelism used to stretch the passphrase on every [save_encrypted].
+const ARGON2_P_COST: u32 = 1;
+
+#[derive(Error, Debug)]
+pub enum DiskError {
+    #[error("i/o error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("the wallet app directory was not initialized, see set_wallet_app_dir")]
+    EmptyAppDir,
+    #[error("invalid name `{0}`")]
+    InvalidName(String),
+    #[error("the stored data does not authenticate the provided passphrase")]
+    DecryptionFailed,
+    #[error("the stored data is not a recognized wallet keystore file")]
+    InvalidFormat,
+}
+
+pub type Result<T> = std::result::Result<T, DiskError>;
+
+/// Directory name where wallet files are stored, analogous to
+/// [crw_preferences's `set_preferences_app_dir`](https://docs.rs/crw-preferences). Must be set
+/// once via [set_wallet_app_dir] before [save_encrypted]/[load_encrypted] are used outside tests.
+static WALLET_APP_DIR: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new(String::new()));
+
+/// Sets the application directory where wallet files are stored. `dir` is just the name of a
+/// directory created inside the user's config directory (e.g. `dirs::config_dir()` on Linux).
+pub fn set_wallet_app_dir(dir: &str) {
+    let mut app_dir = WALLET_APP_DIR.lock().unwrap();
+    app_dir.clear();
+    app_dir.push_str(dir);
+}
+
+/// Returns true if `name` contains only ascii alphanumeric characters or `-`, `_`, `.`, and is
+/// not made of dots alone (so `.` and `..` are rejected).
+fn is_name_valid(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().all(|c| c == '.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ['-', '_', '.'].contains(&c))
+}
+
+/// The directory [get_config_file] resolves `name` under. Uses the current working directory in
+/// tests, so the test suite never touches a real user config directory.
+#[cfg(test)]
+fn base_dir() -> Result<PathBuf> {
+    Ok(PathBuf::new())
+}
+
+#[cfg(not(test))]
+fn base_dir() -> Result<PathBuf> {
+    let app_dir = WALLET_APP_DIR.lock().unwrap();
+    if app_dir.is_empty() {
+        return Err(DiskError::EmptyAppDir);
+    }
+
+    let mut path = dirs::config_dir().ok_or(DiskError::EmptyAppDir)?;
+    path.push(app_dir.as_str());
+    if !path.exists() {
+        fs::create_dir_all(&path)?;
+    }
+    Ok(path)
+}
+
+/// Resolves `name` to a path inside the wallet app directory (see [set_wallet_app_dir]).
+fn get_config_file(name: &str) -> Result<PathBuf> {
+    if !is_name_valid(name) {
+        return Err(DiskError::InvalidName(name.to_owned()));
+    }
+
+    let mut path = base_dir()?;
+    path.push(name);
+    Ok(path)
+}
+
+/// Derives a 256-bit symmetric key from `passphrase` and `salt` with Argon2id, making
+/// brute-forcing a weak passphrase memory-hard instead of mapping it directly onto the key.
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let params = Params::new(ARGON2_M_COST, ARGON2_T_COST, ARGON2_P_COST, Some(32))
+        .map_err(|_| DiskError::DecryptionFailed)?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|_| DiskError::DecryptionFailed)?;
+
+    Ok(key)
+}
+
+/// Encrypts `data` with `passphrase` and writes it to the file named `name` inside the wallet app
+/// directory (see [set_wallet_app_dir]), taking an exclusive file lock for the duration of the
+/// write, released automatically once it goes out of scope.
+///
+/// The file is a versioned `HEADER_MAGIC || HEADER_VERSION || salt || nonce || ciphertext`
+/// envelope: `passphrase` is stretched into a 256-bit key with Argon2id under a fresh random
+/// `salt`, and `data` is sealed with XChaCha20-Poly1305 under a fresh random `nonce`. Both are
+/// stored alongside the ciphertext so [load_encrypted] can reverse the process from `passphrase`
+/// alone.
+///
+/// # Errors
+/// Returns [DiskError::InvalidName] if `name` isn't a valid file name, or [DiskError::Io] if the
+/// file can't be opened or written.
+pub fn save_encrypted(name: &str, data: &[u8], passphrase: &str) -> Result<()> {
+    let path = get_config_file(name)?;
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce_bytes), data)
+        .map_err(|_| DiskError::DecryptionFailed)?;
+
+    let mut envelope =
+        Vec::with_capacity(HEADER_MAGIC.len() + 1 + SALT_LEN + NONCE_LEN + ciphertext.len());
+    envelope.extend_from_slice(HEADER_MAGIC);
+    envelope.push(HEADER_VERSION);
+    envelope.extend_from_slice(&salt);
+    envelope.extend_from_slice(&nonce_bytes);
+    envelope.extend_from_slice(&ciphertext);
+
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .open(&path)?;
+    let mut lock = FileLock::new(file);
+    let mut guard = lock.write()?;
+
+    // A new envelope may be shorter than whatever this file held before; without truncating, a
+    // shrinking rewrite would leave trailing bytes from the previous one appended to the file.
+    guard.set_len(0)?;
+    guard.write_all(&envelope)?;
+    guard.flush()?;
+
+    Ok(())
+}
+
+/// Reads and decrypts the file written by [save_encrypted] under `name`, taking a shared file lock
+/// for the duration of the read, released automatically once it goes out of scope.
+///
+/// # Errors
+/// Returns [DiskError::InvalidFormat] if the file isn't a recognized envelope, and
+/// [DiskError::DecryptionFailed] if `passphrase` doesn't authenticate the stored data.
+pub fn load_encrypted(name: &str, passphrase: &str) -> Result<Vec<u8>> {
+    let path = get_config_file(name)?;
+
+    let file = File::open(&path)?;
+    let mut lock = FileLock::new(file);
+    let mut guard = lock.read()?;
+
+    let mut content = Vec::new();
+    guard.read_to_end(&mut content)?;
+
+    let header_len = HEADER_MAGIC.len() + 1;
+    if content.len() < header_len + SALT_LEN + NONCE_LEN
+        || content[..HEADER_MAGIC.len()] != *HEADER_MAGIC
+    {
+        return Err(DiskError::InvalidFormat);
+    }
+    if content[HEADER_MAGIC.len()] != HEADER_VERSION {
+        return Err(DiskError::InvalidFormat);
+    }
+
+    let mut offset = header_len;
+    let salt = &content[offset..offset + SALT_LEN];
+    offset += SALT_LEN;
+    let nonce = &content[offset..offset + NONCE_LEN];
+    offset += NONCE_LEN;
+    let ciphertext = &content[offset..];
+
+    let key = derive_key(passphrase, salt)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    cipher
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| DiskError::DecryptionFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs as stdfs;
+
+    #[test]
+    fn round_trips_through_disk() {
+        let name = "disk-round-trip.keystore";
+        let data = b"super secret mnemonic".to_vec();
+
+        save_encrypted(name, &data, "correct horse battery staple").unwrap();
+        let loaded = load_encrypted(name, "correct horse battery staple").unwrap();
+
+        assert_eq!(data, loaded);
+        let _ = stdfs::remove_file(name);
+    }
+
+    #[test]
+    fn wrong_passphrase_fails() {
+        let name = "disk-wrong-passphrase.keystore";
+        save_encrypted(name, b"some data", "right passphrase").unwrap();
+
+        let result = load_encrypted(name, "wrong passphrase");
+
+        assert!(matches!(result, Err(DiskError::DecryptionFailed)));
+        let _ = stdfs::remove_file(name);
+    }
+
+    #[test]
+    fn rejects_invalid_name() {
+        let result = save_encrypted("not valid!", b"data", "passphrase");
+        assert!(matches!(result, Err(DiskError::InvalidName(_))));
+    }
+
+    #[test]
+    fn overwriting_with_shorter_data_does_not_leave_trailing_bytes() {
+        let name = "disk-shrink.keystore";
+
+        save_encrypted(name, b"a fairly long secret payload", "passphrase").unwrap();
+        save_encrypted(name, b"short", "passphrase").unwrap();
+
+        let loaded = load_encrypted(name, "passphrase").unwrap();
+
+        assert_eq!(b"short".to_vec(), loaded);
+        let _ = stdfs::remove_file(name);
+    }
+}
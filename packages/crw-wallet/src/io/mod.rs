@@ -0,0 +1,4 @@
+//! Direct-to-disk storage for wallet material (mnemonics, seeds, keystores), independent of the
+//! [crw_preferences::preferences::Preferences] abstraction [crate::keyring::Keyring] is built on.
+
+pub mod disk;
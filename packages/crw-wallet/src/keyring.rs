@@ -0,0 +1,148 @@
+//! A named collection of [MnemonicWallet]s, persisted through a [Preferences] backend so the
+//! same code works on mobile storage, wasm, and disk.
+
+use crate::crypto::MnemonicWallet;
+use crate::WalletError;
+use crw_preferences::preferences::Preferences;
+use serde::{Deserialize, Serialize};
+
+/// What's actually stored under each name: a wallet's encrypted keystore blob plus the
+/// derivation path it was derived with, which [MnemonicWallet::to_keystore] deliberately leaves
+/// out of the keystore document itself.
+#[derive(Serialize, Deserialize)]
+struct KeyringEntry {
+    derivation_path: String,
+    keystore: String,
+}
+
+/// Manages a set of named [MnemonicWallet]s backed by a [Preferences] store, mirroring how
+/// relayer key-management layers keep multiple signer identities addressable by name. Every
+/// wallet is kept on disk only as an encrypted keystore blob, never as a plaintext mnemonic.
+pub struct Keyring<P: Preferences> {
+    prefs: P,
+    password: String,
+}
+
+impl<P: Preferences> Keyring<P> {
+    /// Wraps `prefs`, encrypting and decrypting every entry with `password`.
+    pub fn new(prefs: P, password: &str) -> Keyring<P> {
+        Keyring {
+            prefs,
+            password: password.to_owned(),
+        }
+    }
+
+    /// Encrypts `wallet` with this keyring's password and stores it under `name`, replacing
+    /// anything previously stored there, then persists the change to disk.
+    ///
+    /// # Errors
+    /// Returns the usual [MnemonicWallet::to_keystore] errors, or [WalletError::Preferences] if
+    /// the underlying store can't be written to.
+    pub fn add(&mut self, name: &str, wallet: &MnemonicWallet) -> Result<(), WalletError> {
+        let entry = KeyringEntry {
+            derivation_path: wallet.get_derivation_path().to_owned(),
+            keystore: wallet.to_keystore(&self.password)?,
+        };
+        let serialized = serde_json::to_string(&entry)
+            .map_err(|err| WalletError::Keystore(err.to_string()))?;
+
+        self.prefs
+            .put_str(name, serialized)
+            .map_err(|err| WalletError::Preferences(err.to_string()))?;
+        self.prefs
+            .save()
+            .map_err(|err| WalletError::Preferences(err.to_string()))
+    }
+
+    /// Decrypts and returns the wallet stored under `name`, or `None` if there's no entry under
+    /// that name or it can't be decrypted with this keyring's password.
+    pub fn get(&self, name: &str) -> Option<MnemonicWallet> {
+        let serialized = self.prefs.get_str(name)?;
+        let entry: KeyringEntry = serde_json::from_str(&serialized).ok()?;
+
+        MnemonicWallet::from_keystore(&entry.keystore, &self.password, &entry.derivation_path).ok()
+    }
+
+    /// Returns the names of every wallet currently stored in this keyring.
+    pub fn list(&self) -> Vec<String> {
+        self.prefs.keys()
+    }
+
+    /// Removes the wallet stored under `name`, if any, and persists the change to disk.
+    ///
+    /// # Errors
+    /// Returns [WalletError::Preferences] if the underlying store can't be written to.
+    pub fn remove(&mut self, name: &str) -> Result<(), WalletError> {
+        self.prefs.remove(name);
+        self.prefs
+            .save()
+            .map_err(|err| WalletError::Preferences(err.to_string()))
+    }
+
+    /// Finds the first wallet in this keyring whose bech32 address for `hrp` equals `address`.
+    pub fn get_by_address(&self, hrp: &str, address: &str) -> Option<MnemonicWallet> {
+        self.list().into_iter().find_map(|name| {
+            let wallet = self.get(&name)?;
+            (wallet.get_bech32_address(hrp).ok()?.as_str() == address).then_some(wallet)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crw_preferences::unencrypted::UnencryptedPreferences;
+
+    static DERIVATION_PATH: &str = "m/44'/118'/0'/0/0";
+    static TEST_MNEMONIC: &str = "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel";
+
+    #[test]
+    fn add_get_list_remove() {
+        let prefs = UnencryptedPreferences::new("keyring-add-get-list-remove").unwrap();
+        let mut keyring = Keyring::new(prefs, "super secret password");
+
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DERIVATION_PATH).unwrap();
+        keyring.add("alice", &wallet).unwrap();
+
+        assert_eq!(keyring.list(), vec!["alice".to_owned()]);
+
+        let restored = keyring.get("alice").unwrap();
+        assert_eq!(
+            wallet.get_bech32_address("cosmos").unwrap(),
+            restored.get_bech32_address("cosmos").unwrap()
+        );
+
+        keyring.remove("alice").unwrap();
+        assert!(keyring.get("alice").is_none());
+        assert!(keyring.list().is_empty());
+
+        keyring.prefs.erase();
+    }
+
+    #[test]
+    fn get_missing_entry_returns_none() {
+        let prefs = UnencryptedPreferences::new("keyring-get-missing-entry").unwrap();
+        let keyring = Keyring::new(prefs, "super secret password");
+
+        assert!(keyring.get("nobody").is_none());
+    }
+
+    #[test]
+    fn get_by_address_finds_matching_wallet() {
+        let prefs = UnencryptedPreferences::new("keyring-get-by-address").unwrap();
+        let mut keyring = Keyring::new(prefs, "super secret password");
+
+        let wallet = MnemonicWallet::new(TEST_MNEMONIC, DERIVATION_PATH).unwrap();
+        let address = wallet.get_bech32_address("cosmos").unwrap();
+        keyring.add("alice", &wallet).unwrap();
+
+        let found = keyring.get_by_address("cosmos", &address).unwrap();
+        assert_eq!(address, found.get_bech32_address("cosmos").unwrap());
+
+        assert!(keyring
+            .get_by_address("cosmos", "cosmos1doesnotexist")
+            .is_none());
+
+        keyring.prefs.erase();
+    }
+}
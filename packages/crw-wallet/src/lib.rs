@@ -4,6 +4,9 @@ extern crate ffi_helpers;
 
 pub mod crypto;
 mod error;
+pub mod io;
+pub mod keyring;
+pub mod seed;
 pub use crate::error::WalletError;
 
 #[cfg(feature = "wasm-bindgen")]
@@ -0,0 +1,107 @@
+//! Zero-config, "it just remembers my wallet" seed bootstrap, as popularized by the xmr-btc-swap
+//! daemon: on first launch a [Seed] is generated and persisted; on every later launch the same
+//! [Seed] is recovered instead, so callers never have to supply (or store) a mnemonic themselves.
+
+use crate::error::WalletError;
+use crate::io::disk;
+use bip39::{Language, Mnemonic, MnemonicType};
+
+/// A BIP-39 mnemonic phrase recovered (or generated) by [Seed::from_file_or_generate]. Deliberately
+/// opaque beyond [Seed::phrase]: callers are expected to feed it into
+/// [crate::crypto::MnemonicWallet::from_seed] rather than handle the phrase themselves.
+pub struct Seed {
+    phrase: String,
+}
+
+impl Seed {
+    /// Loads the [Seed] persisted under `dir` (see [disk::set_wallet_app_dir]), generating and
+    /// persisting a new 24-word mnemonic the first time it's called for a given `dir`.
+    ///
+    /// The mnemonic is stored through [disk::save_encrypted] under `passphrase`, so losing
+    /// `passphrase` means losing access to the recovered wallet just as it would with any other
+    /// keystore in this crate.
+    ///
+    /// # Errors
+    /// Returns a [WalletError::Keystore] if the file exists but can't be read or decrypted, or if
+    /// a freshly generated seed can't be written.
+    pub fn from_file_or_generate(dir: &str, passphrase: &str) -> Result<Seed, WalletError> {
+        disk::set_wallet_app_dir(dir);
+        let file_name = seed_file_name(dir);
+
+        match disk::load_encrypted(&file_name, passphrase) {
+            Ok(phrase_bytes) => {
+                let phrase = String::from_utf8(phrase_bytes)
+                    .map_err(|err| WalletError::Keystore(err.to_string()))?;
+                Ok(Seed { phrase })
+            }
+            Err(disk::DiskError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+                let mnemonic = Mnemonic::new(MnemonicType::Words24, Language::English);
+                let phrase = mnemonic.phrase().to_owned();
+                disk::save_encrypted(&file_name, phrase.as_bytes(), passphrase)
+                    .map_err(|err| WalletError::Keystore(err.to_string()))?;
+                Ok(Seed { phrase })
+            }
+            Err(err) => Err(WalletError::Keystore(err.to_string())),
+        }
+    }
+
+    /// The recovered (or freshly generated) BIP-39 mnemonic phrase.
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+}
+
+/// Derives the keystore file name a [Seed] is persisted under from `dir`, so that app directories
+/// with different names get different files (this matters in tests, where [disk::base_dir]
+/// always resolves to the current directory regardless of `dir`). Every character of `dir` other
+/// than an ascii alphanumeric one becomes `_`, so names that differ only in such characters
+/// (e.g. `a-b` and `a_b`) share a file.
+fn seed_file_name(dir: &str) -> String {
+    let sanitized: String = dir
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{sanitized}-seed.keystore")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn generates_then_recovers_the_same_seed() {
+        let dir = "seed-test-generates-then-recovers";
+
+        let first = Seed::from_file_or_generate(dir, "passphrase").unwrap();
+        let second = Seed::from_file_or_generate(dir, "passphrase").unwrap();
+
+        assert_eq!(first.phrase(), second.phrase());
+        assert_eq!(24, first.phrase().split_whitespace().count());
+        let _ = fs::remove_file(seed_file_name(dir));
+    }
+
+    #[test]
+    fn distinct_dirs_generate_distinct_seeds() {
+        let dir_a = "seed-test-distinct-a";
+        let dir_b = "seed-test-distinct-b";
+
+        let seed_a = Seed::from_file_or_generate(dir_a, "passphrase").unwrap();
+        let seed_b = Seed::from_file_or_generate(dir_b, "passphrase").unwrap();
+
+        assert_ne!(seed_a.phrase(), seed_b.phrase());
+        let _ = fs::remove_file(seed_file_name(dir_a));
+        let _ = fs::remove_file(seed_file_name(dir_b));
+    }
+
+    #[test]
+    fn wrong_passphrase_fails_to_recover() {
+        let dir = "seed-test-wrong-passphrase";
+        Seed::from_file_or_generate(dir, "correct passphrase").unwrap();
+
+        let result = Seed::from_file_or_generate(dir, "wrong passphrase");
+
+        assert!(matches!(result, Err(WalletError::Keystore(_))));
+        let _ = fs::remove_file(seed_file_name(dir));
+    }
+}
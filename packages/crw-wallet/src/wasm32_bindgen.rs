@@ -49,3 +49,45 @@ pub fn random_mnemonic() -> String {
     let mnemonic = Mnemonic::new(MnemonicType::Words24, Language::English);
     return mnemonic.phrase().to_string();
 }
+
+/// Bech32 data-part alphabet. Notably excludes `1`, `b`, `i` and `o` to avoid visual ambiguity.
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Derivation path used to search for a vanity address.
+const VANITY_DERIVATION_PATH: &str = "m/44'/118'/0'/0/0";
+
+/// Generates a random 24-word mnemonic whose bech32 address, derived at
+/// `m/44'/118'/0'/0/0`, starts with `prefix` right after the `hrp1` separator.
+///
+/// The browser runs this search on a single thread, so progress is bounded by `max_attempts`
+/// rather than a wall-clock timeout. Every extra character in `prefix` multiplies the expected
+/// number of attempts by roughly 32, since the bech32 alphabet has 32 symbols.
+#[wasm_bindgen(js_name = generateVanityMnemonic)]
+pub fn generate_vanity_mnemonic(hrp: &str, prefix: &str, max_attempts: u32) -> Result<String, JsValue> {
+    if let Some(invalid) = prefix.chars().find(|c| !BECH32_CHARSET.contains(*c)) {
+        return Err(JsValue::from(format!(
+            "'{}' is not part of the bech32 charset",
+            invalid
+        )));
+    }
+
+    let needle = format!("{}1{}", hrp, prefix);
+
+    for _ in 0..max_attempts {
+        let mnemonic = Mnemonic::new(MnemonicType::Words24, Language::English);
+        let phrase = mnemonic.phrase().to_owned();
+
+        let address = MnemonicWallet::new(&phrase, VANITY_DERIVATION_PATH)
+            .ok()
+            .and_then(|wallet| wallet.get_bech32_address(hrp).ok());
+
+        if address.map_or(false, |addr| addr.starts_with(&needle)) {
+            return Ok(phrase);
+        }
+    }
+
+    Err(JsValue::from(format!(
+        "no address matching prefix '{}' found within {} attempts",
+        prefix, max_attempts
+    )))
+}
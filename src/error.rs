@@ -23,5 +23,20 @@ pub enum Error {
     PrivateKey(String),
 
     #[error("Bech 32 encoding error: {0}")]
-    Bech32(String)
+    Bech32(String),
+
+    #[error("Keystore error: {0}")]
+    Keystore(String),
+
+    #[error("Derivation path error: {0}")]
+    DerivationPath(String),
+
+    #[error("Vanity search error: {0}")]
+    Vanity(String),
+
+    #[error("Verification error: {0}")]
+    Verify(String),
+
+    #[error("Not enough signatures collected: {0}")]
+    NotEnoughSignatures(String),
 }
\ No newline at end of file
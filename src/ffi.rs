@@ -0,0 +1,299 @@
+//! FFI entry points to construct a [`Wallet`] and to export/import it as a portable encrypted
+//! file.
+//!
+//! The encrypted blob layout is: a 2-byte magic, a 1-byte version, a length-prefixed hrp string,
+//! a length-prefixed derivation path, a 16-byte scrypt salt, a 12-byte AES-GCM nonce, a 4-byte
+//! checksum of the plaintext entropy and finally the encrypted BIP-39 entropy. Only the
+//! entropy is ever encrypted and stored, never the mnemonic phrase itself.
+
+use crate::error::Error;
+use crate::wallet::{Bip44, Wallet};
+use aes_gcm::aead::{Aead, NewAead};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use bip39::{Language, Mnemonic};
+use libc::{c_char, c_uchar, c_uint, size_t};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use scrypt::{scrypt, Params};
+use sha2::{Digest, Sha256};
+use std::ffi::CStr;
+use std::ptr::null_mut;
+use std::slice;
+
+/// Derive a [`Wallet`] from the given mnemonic, derivation path and human readable part.
+/// The returned ptr must be freed using the [`wallet_free`] function to avoid memory leaks.
+///
+/// # Errors
+/// This function returns a nullptr in case of error and stores the error cause in thread-local
+/// storage, retrievable with [error_message_utf8](ffi_helpers::error_handling::error_message_utf8).
+#[no_mangle]
+pub extern "C" fn wallet_from_mnemonic(
+    mnemonic: *const c_char,
+    derivation_path: *const c_char,
+    hrp: *const c_char,
+) -> *mut Wallet {
+    null_pointer_check!(mnemonic);
+    null_pointer_check!(derivation_path);
+    null_pointer_check!(hrp);
+
+    let mnemonic = unsafe { CStr::from_ptr(mnemonic).to_string_lossy() };
+    let derivation_path = unsafe { CStr::from_ptr(derivation_path).to_string_lossy() };
+    let hrp = unsafe { CStr::from_ptr(hrp).to_string_lossy() };
+
+    match Wallet::from_mnemonic(mnemonic.as_ref(), derivation_path.into_owned(), hrp.into_owned())
+    {
+        Ok(wallet) => Box::into_raw(Box::new(wallet)),
+        Err(e) => {
+            ffi_helpers::update_last_error(e);
+            null_mut()
+        }
+    }
+}
+
+/// Derive a [`Wallet`] from the given mnemonic and derivation path, folding `passphrase` (the
+/// BIP-39 "25th word") into the seed. This is the only way to open a wallet that was created
+/// with a passphrase; an empty passphrase behaves like [`wallet_from_mnemonic`].
+/// The returned ptr must be freed using the [`wallet_free`] function to avoid memory leaks.
+///
+/// # Errors
+/// This function returns a nullptr in case of error and stores the error cause in thread-local
+/// storage, retrievable with [error_message_utf8](ffi_helpers::error_handling::error_message_utf8).
+#[no_mangle]
+pub extern "C" fn wallet_from_mnemonic_with_passphrase(
+    mnemonic: *const c_char,
+    passphrase: *const c_char,
+    derivation_path: *const c_char,
+    hrp: *const c_char,
+) -> *mut Wallet {
+    null_pointer_check!(mnemonic);
+    null_pointer_check!(passphrase);
+    null_pointer_check!(derivation_path);
+    null_pointer_check!(hrp);
+
+    let mnemonic = unsafe { CStr::from_ptr(mnemonic).to_string_lossy() };
+    let passphrase = unsafe { CStr::from_ptr(passphrase).to_string_lossy() };
+    let derivation_path = unsafe { CStr::from_ptr(derivation_path).to_string_lossy() };
+    let hrp = unsafe { CStr::from_ptr(hrp).to_string_lossy() };
+
+    match Wallet::from_mnemonic_with_passphrase(
+        mnemonic.as_ref(),
+        passphrase.as_ref(),
+        derivation_path.into_owned(),
+        hrp.into_owned(),
+    ) {
+        Ok(wallet) => Box::into_raw(Box::new(wallet)),
+        Err(e) => {
+            ffi_helpers::update_last_error(e);
+            null_mut()
+        }
+    }
+}
+
+/// Derive a [`Wallet`] using a structured BIP-44 path `m/44'/coin_type'/account'/change/index`,
+/// folding `passphrase` (the BIP-39 "25th word") into the seed. Iterating `account` from a
+/// single mnemonic is how HD wallet UIs discover additional accounts.
+/// The returned ptr must be freed using the [`wallet_free`] function to avoid memory leaks.
+///
+/// # Errors
+/// This function returns a nullptr in case of error and stores the error cause in thread-local
+/// storage, retrievable with [error_message_utf8](ffi_helpers::error_handling::error_message_utf8).
+#[no_mangle]
+pub extern "C" fn wallet_from_mnemonic_bip44(
+    mnemonic: *const c_char,
+    passphrase: *const c_char,
+    coin_type: c_uint,
+    account: c_uint,
+    change: c_uint,
+    index: c_uint,
+    hrp: *const c_char,
+) -> *mut Wallet {
+    null_pointer_check!(mnemonic);
+    null_pointer_check!(passphrase);
+    null_pointer_check!(hrp);
+
+    let mnemonic = unsafe { CStr::from_ptr(mnemonic).to_string_lossy() };
+    let passphrase = unsafe { CStr::from_ptr(passphrase).to_string_lossy() };
+    let hrp = unsafe { CStr::from_ptr(hrp).to_string_lossy() };
+
+    match Wallet::from_mnemonic_bip44(
+        mnemonic.as_ref(),
+        passphrase.as_ref(),
+        Bip44 { coin_type, account, change, address_index: index },
+        hrp.into_owned(),
+    ) {
+        Ok(wallet) => Box::into_raw(Box::new(wallet)),
+        Err(e) => {
+            ffi_helpers::update_last_error(e);
+            null_mut()
+        }
+    }
+}
+
+/// Deallocate a [`Wallet`] instance.
+#[no_mangle]
+pub extern "C" fn wallet_free(ptr: *mut Wallet) {
+    if ptr.is_null() {
+        return;
+    }
+
+    Box::from(ptr);
+}
+
+const MAGIC: [u8; 2] = *b"CW";
+const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const CHECKSUM_LEN: usize = 4;
+
+/// Derives a 32-byte key from `password` and `salt` with scrypt (N=16384, r=8, p=1).
+fn derive_key(password: &str, salt: &[u8]) -> [u8; 32] {
+    let params = Params::new(14, 8, 1, 32).unwrap();
+    let mut key = [0u8; 32];
+    scrypt(password.as_bytes(), salt, &params, &mut key).unwrap();
+    key
+}
+
+/// Exports `wallet` to a self-contained encrypted blob that can be restored on another device
+/// with [`import_encrypted`], without ever persisting the plaintext mnemonic phrase.
+pub(crate) fn export_encrypted(wallet: &Wallet, password: &str) -> Result<Vec<u8>, Error> {
+    let hrp_bytes = wallet.hrp().as_bytes();
+    let dp_bytes = wallet.derivation_path().as_bytes();
+    let entropy = wallet.entropy();
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(password, &salt);
+    let cipher = Aes256Gcm::new(Key::from_slice(&key));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), entropy.as_slice())
+        .map_err(|err| Error::Keystore(err.to_string()))?;
+
+    let checksum = Sha256::digest(&entropy);
+
+    let mut blob = Vec::new();
+    blob.extend_from_slice(&MAGIC);
+    blob.push(VERSION);
+    blob.push(hrp_bytes.len() as u8);
+    blob.extend_from_slice(hrp_bytes);
+    blob.push(dp_bytes.len() as u8);
+    blob.extend_from_slice(dp_bytes);
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&checksum[..CHECKSUM_LEN]);
+    blob.extend_from_slice(&ciphertext);
+
+    Ok(blob)
+}
+
+/// Exports `wallet` to a self-contained encrypted blob that can be restored on another device
+/// with [`wallet_import_encrypted`], without ever persisting the plaintext mnemonic phrase.
+///
+/// The returned buffer's length is stored into `out_len`. Returns a nullptr on error. The
+/// returned buffer must be freed with [`wallet_export_free`].
+#[no_mangle]
+pub extern "C" fn wallet_export_encrypted(
+    wallet: *const Wallet,
+    password: *const c_char,
+    out_len: *mut size_t,
+) -> *mut c_uchar {
+    if wallet.is_null() || password.is_null() || out_len.is_null() {
+        return null_mut();
+    }
+
+    let wallet = unsafe { &*wallet };
+    let password = unsafe { CStr::from_ptr(password) }.to_string_lossy();
+
+    let blob = match export_encrypted(wallet, &password) {
+        Ok(blob) => blob,
+        Err(_) => return null_mut(),
+    };
+
+    let mut blob = blob.into_boxed_slice();
+    unsafe { *out_len = blob.len() };
+    let ptr = blob.as_mut_ptr();
+    std::mem::forget(blob);
+    ptr
+}
+
+/// Releases a buffer returned from [`wallet_export_encrypted`].
+#[no_mangle]
+pub extern "C" fn wallet_export_free(ptr: *mut c_uchar, len: size_t) {
+    if ptr.is_null() {
+        return;
+    }
+    unsafe { drop(Vec::from_raw_parts(ptr, len, len)) };
+}
+
+/// Imports a wallet previously produced by [`wallet_export_encrypted`].
+///
+/// Returns a nullptr if `password` is wrong, the blob is corrupted, or the stored checksum
+/// does not match the decrypted entropy.
+#[no_mangle]
+pub extern "C" fn wallet_import_encrypted(
+    blob: *const c_uchar,
+    len: size_t,
+    password: *const c_char,
+) -> *mut Wallet {
+    if blob.is_null() || password.is_null() {
+        return null_mut();
+    }
+
+    let data = unsafe { slice::from_raw_parts(blob, len) };
+    let password = unsafe { CStr::from_ptr(password) }.to_string_lossy();
+
+    match import_encrypted(data, password.as_ref()) {
+        Ok(wallet) => Box::into_raw(Box::new(wallet)),
+        Err(_) => null_mut(),
+    }
+}
+
+pub(crate) fn import_encrypted(data: &[u8], password: &str) -> Result<Wallet, Error> {
+    let header_len = MAGIC.len() + 1;
+    if data.len() < header_len || data[..MAGIC.len()] != MAGIC {
+        return Err(Error::Keystore("invalid magic".to_string()));
+    }
+
+    let mut offset = MAGIC.len();
+    let version = data[offset];
+    offset += 1;
+    if version != VERSION {
+        return Err(Error::Keystore("unsupported version".to_string()));
+    }
+
+    let hrp_len = data[offset] as usize;
+    offset += 1;
+    let hrp = String::from_utf8_lossy(&data[offset..offset + hrp_len]).to_string();
+    offset += hrp_len;
+
+    let dp_len = data[offset] as usize;
+    offset += 1;
+    let derivation_path = String::from_utf8_lossy(&data[offset..offset + dp_len]).to_string();
+    offset += dp_len;
+
+    let salt = &data[offset..offset + SALT_LEN];
+    offset += SALT_LEN;
+    let nonce_bytes = &data[offset..offset + NONCE_LEN];
+    offset += NONCE_LEN;
+    let checksum = &data[offset..offset + CHECKSUM_LEN];
+    offset += CHECKSUM_LEN;
+    let ciphertext = &data[offset..];
+
+    let key = derive_key(password, salt);
+    let cipher = Aes256Gcm::new(Key::from_slice(&key));
+    let entropy = cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| Error::Keystore("decryption failed".to_string()))?;
+
+    let actual_checksum = Sha256::digest(&entropy);
+    if &actual_checksum[..CHECKSUM_LEN] != checksum {
+        return Err(Error::Keystore("checksum mismatch".to_string()));
+    }
+
+    let mnemonic = Mnemonic::from_entropy(&entropy, Language::English)
+        .map_err(|err| Error::Mnemonic(err.to_string()))?;
+
+    Wallet::from_mnemonic(mnemonic.phrase(), derivation_path, hrp)
+}
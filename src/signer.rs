@@ -3,15 +3,20 @@ use crate::{
     rpc::{
         ChainClient,
         get_node_info,
+        NodeInfo,
         NodeInfoResponse
     },
     wallet::{
         Wallet,
-        WalletJS
+        WalletJS,
+        TxSignature,
+        Bip44
     },
     error::Error
 };
 use crate::msg::Msg;
+use cosmos_sdk_proto::cosmos::{auth::v1beta1::BaseAccount, tx::v1beta1::Fee};
+use prost_types::Any;
 
 
 /// Import a wallet from the given mnemonic
@@ -28,12 +33,134 @@ pub fn import_wallet(mnemonic: &str, derivation_path: &str, hrp: &str) -> Result
  Ok(JsValue::from_serde(&wallet).unwrap())
 }
 
+/// Import a wallet from the given mnemonic, folding `passphrase` (the BIP-39 "25th word") into
+/// the seed. This is the only way to open a wallet that was created with a passphrase.
+#[wasm_bindgen(js_name = "importWalletWithPassphrase")]
+pub fn import_wallet_with_passphrase(
+    mnemonic: &str,
+    passphrase: &str,
+    derivation_path: &str,
+    hrp: &str,
+) -> Result<JsValue, JsValue> {
+    let wallet: WalletJS = Wallet::from_mnemonic_with_passphrase(
+        mnemonic,
+        passphrase,
+        derivation_path.to_string(),
+        hrp.to_string(),
+    )
+        .map_err(| error | JsValue::from_serde(&error).unwrap())?
+        .into();
 
-/// Sign and send a transaction with the given wallet
-#[wasm_bindgen(js_name = "signAndSendMsg")]
-pub fn sign_and_send_msg(wallet: &JsValue, msg: &JsValue, lcd_addr: &str, grpc_addr: &str) -> Result<JsValue, JsValue> {
-    let walletJS : WalletJS = wallet.into_serde().unwrap();
-    let msg: Msg = msg.into_serde().unwrap();
+    Ok(JsValue::from_serde(&wallet).unwrap())
+}
+
+/// Import a wallet using a structured BIP-44 path `m/44'/coin_type'/account'/change/index`,
+/// folding `passphrase` (the BIP-39 "25th word") into the seed. Iterating `account` from a
+/// single mnemonic is how HD wallet UIs discover additional accounts.
+#[wasm_bindgen(js_name = "importWalletBip44")]
+pub fn import_wallet_bip44(
+    mnemonic: &str,
+    passphrase: &str,
+    coin_type: u32,
+    account: u32,
+    change: u32,
+    index: u32,
+    hrp: &str,
+) -> Result<JsValue, JsValue> {
+    let wallet: WalletJS = Wallet::from_mnemonic_bip44(
+        mnemonic,
+        passphrase,
+        Bip44 { coin_type, account, change, address_index: index },
+        hrp.to_string(),
+    )
+        .map_err(| error | JsValue::from_serde(&error).unwrap())?
+        .into();
+
+    Ok(JsValue::from_serde(&wallet).unwrap())
+}
+
+
+/// Builds the canonical sign-doc bytes for a transaction, without touching any private key.
+/// This is the first of three stages (`buildSignDoc` -> `signSignDoc` -> `assembleTx`) that let
+/// a watch-only device, an offline signer and a broadcaster each handle one part of a
+/// transaction, none of them needing both network access and the private key at once.
+#[wasm_bindgen(js_name = "buildSignDoc")]
+pub fn build_sign_doc(
+    account: &JsValue,
+    node_info: &JsValue,
+    msgs: &JsValue,
+    fee: &JsValue,
+    memo: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let account: BaseAccount = account.into_serde().unwrap();
+    let node_info: NodeInfo = node_info.into_serde().unwrap();
+    let msgs: Vec<Msg> = msgs.into_serde().unwrap();
+    let fee: Fee = fee.into_serde().unwrap();
+
+    let sign_doc_bytes = crate::wallet::build_sign_doc(&account, &node_info, &msgs, fee, memo)
+        .map_err(|error| JsValue::from_serde(&error).unwrap())?;
+
+    Ok(JsValue::from_serde(&sign_doc_bytes).unwrap())
+}
+
+/// Signs the bytes produced by [`build_sign_doc`] with the given wallet's private key. Returns
+/// the signature together with the public key needed to verify it; never touches the network.
+#[wasm_bindgen(js_name = "signSignDoc")]
+pub fn sign_sign_doc(wallet: &JsValue, sign_doc_bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+    let wallet_js: WalletJS = wallet.into_serde().unwrap();
+    let wallet: Wallet = wallet_js.into();
+
+    let tx_signature = crate::wallet::sign_sign_doc(&wallet, &sign_doc_bytes)
+        .map_err(|error| JsValue::from_serde(&error).unwrap())?;
+
+    Ok(JsValue::from_serde(&(tx_signature.public_key.value, tx_signature.signature)).unwrap())
+}
+
+/// Assembles broadcast-ready tx bytes from a sign doc and one or more (public key, signature)
+/// pairs collected from [`sign_sign_doc`]. Accepting more than one pair naturally extends to
+/// multisig accounts and joint transactions. Broadcasting is a separate step, left to the
+/// caller's [`ChainClient`].
+#[wasm_bindgen(js_name = "assembleTx")]
+pub fn assemble_tx(sign_doc_bytes: Vec<u8>, signatures: &JsValue) -> Result<JsValue, JsValue> {
+    let signatures: Vec<(Vec<u8>, Vec<u8>)> = signatures.into_serde().unwrap();
+    let tx_signatures: Vec<TxSignature> = signatures
+        .into_iter()
+        .map(|(public_key_bytes, signature)| TxSignature {
+            public_key: Any {
+                type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+                value: public_key_bytes,
+            },
+            signature,
+        })
+        .collect();
+
+    let tx_bytes = crate::wallet::assemble_tx(&sign_doc_bytes, &tx_signatures)
+        .map_err(|error| JsValue::from_serde(&error).unwrap())?;
+
+    Ok(JsValue::from_serde(&tx_bytes).unwrap())
+}
+
+/// Export the given wallet to a password-encrypted, base64-encoded blob that can later be
+/// restored with [`import_encrypted`].
+#[wasm_bindgen(js_name = "exportEncrypted")]
+pub fn export_encrypted(wallet: &JsValue, password: &str) -> Result<JsValue, JsValue> {
+    let wallet_js: WalletJS = wallet.into_serde().unwrap();
+    let wallet: Wallet = wallet_js.into();
+
+    let blob = crate::ffi::export_encrypted(&wallet, password)
+        .map_err(|error| JsValue::from_serde(&error).unwrap())?;
+
+    Ok(JsValue::from_serde(&base64::encode(blob)).unwrap())
+}
+
+/// Import a wallet from a blob produced by [`export_encrypted`]
+#[wasm_bindgen(js_name = "importEncrypted")]
+pub fn import_encrypted(blob: &str, password: &str) -> Result<JsValue, JsValue> {
+    let data = base64::decode(blob).map_err(|error| JsValue::from_serde(&error.to_string()).unwrap())?;
+
+    let wallet: WalletJS = crate::ffi::import_encrypted(&data, password)
+        .map_err(|error| JsValue::from_serde(&error).unwrap())?
+        .into();
 
-    let wallet = walletJS.into()
+    Ok(JsValue::from_serde(&wallet).unwrap())
 }
\ No newline at end of file
@@ -16,20 +16,23 @@ use bitcoin::{
 };
 use cosmos_sdk_proto::cosmos::{
     auth::v1beta1::BaseAccount,
+    crypto::multisig::{v1beta1::CompactBitArray, LegacyAminoPubKey},
     tx::v1beta1::{
-        mode_info::{Single, Sum},
+        mode_info::{Multi, Single, Sum},
         AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw,
     },
 };
 use hdpath::StandardHDPath;
-use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+use k256::ecdsa::{
+    recoverable, signature::Signer, signature::Verifier, Signature, SigningKey, VerifyingKey,
+};
 use prost_types::Any;
 use ripemd160::Ripemd160;
 use sha2::{Digest, Sha256};
 
 use crate::error::Error;
 use crate::msg::Msg;
-use crate::rpc::ChainClient;
+use crate::rpc::{ChainClient, NodeInfo};
 
 /// Keychain contains a pair of Secp256k1 keys.
 pub struct Keychain {
@@ -37,11 +40,59 @@ pub struct Keychain {
     pub ext_private_key: ExtendedPrivKey,
 }
 
+/// A structured BIP-44 derivation path `m/44'/coin_type'/account'/change/address_index`, for
+/// callers that want to assemble a path from its components instead of formatting (and risking
+/// malforming) the string themselves. Mirrors the `Bip44` type the iota-sdk introduced for the
+/// same purpose.
+///
+/// The `Default` implementation is the Cosmos Hub path: coin type 118, account 0, external chain
+/// 0, index 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bip44 {
+    pub coin_type: u32,
+    pub account: u32,
+    pub change: u32,
+    pub address_index: u32,
+}
+
+impl Default for Bip44 {
+    fn default() -> Self {
+        Bip44 {
+            coin_type: 118,
+            account: 0,
+            change: 0,
+            address_index: 0,
+        }
+    }
+}
+
+impl std::fmt::Display for Bip44 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "m/44'/{}'/{}'/{}/{}",
+            self.coin_type, self.account, self.change, self.address_index
+        )
+    }
+}
+
+impl Bip44 {
+    /// Parses this path into a [`StandardHDPath`], returning [`Error::DerivationPath`] rather
+    /// than panicking if the resulting string is somehow malformed.
+    pub fn to_hd_path(&self) -> Result<StandardHDPath, Error> {
+        StandardHDPath::try_from(self.to_string().as_str())
+            .map_err(|_| Error::DerivationPath(self.to_string()))
+    }
+}
+
 /// Wallet is a facility used to manipulate private and public keys associated
 /// to a BIP-32 mnemonic.
 pub struct Wallet {
     pub keychain: Keychain,
     pub bech32_address: String,
+    mnemonic: Mnemonic,
+    derivation_path: String,
+    hrp: String,
 }
 
 impl Wallet {
@@ -50,28 +101,88 @@ impl Wallet {
         mnemonic_words: &str,
         derivation_path: String,
         hrp: String,
+    ) -> Result<Wallet, Error> {
+        Wallet::from_mnemonic_with_passphrase(mnemonic_words, "", derivation_path, hrp)
+    }
+
+    /// Derive a Wallet from the given mnemonic_words and derivation path, folding `passphrase`
+    /// (the BIP-39 "25th word") into the seed. This is the only way to open a wallet that was
+    /// created with a passphrase; an empty passphrase is equivalent to [`Wallet::from_mnemonic`].
+    pub fn from_mnemonic_with_passphrase(
+        mnemonic_words: &str,
+        passphrase: &str,
+        derivation_path: String,
+        hrp: String,
     ) -> Result<Wallet, Error> {
         // Create mnemonic and generate seed from it
         let mnemonic =
             Mnemonic::from_phrase(mnemonic_words, Language::English)
                 .map_err(|err| Error::Mnemonic(err.to_string()))?;
-        let seed = Seed::new(&mnemonic, "");
+        let seed = Seed::new(&mnemonic, passphrase);
 
         // Set hd_path for master_key generation
-        let hd_path = StandardHDPath::try_from(derivation_path.as_str()).unwrap();
+        let hd_path = StandardHDPath::try_from(derivation_path.as_str())
+            .map_err(|_| Error::DerivationPath(derivation_path.clone()))?;
 
         let keychain = generate_keychain(hd_path, seed)?;
 
-        let bech32_address = bech32_address_from_public_key(keychain.ext_public_key.clone(), hrp)?;
+        let bech32_address =
+            bech32_address_from_public_key(keychain.ext_public_key.clone(), hrp.clone())?;
 
         let wallet = Wallet {
             keychain,
             bech32_address,
+            mnemonic,
+            derivation_path,
+            hrp,
         };
 
         Ok(wallet)
     }
 
+    /// Derive a Wallet using a structured [`Bip44`] path, folding `passphrase` (the BIP-39 "25th
+    /// word") into the seed. Iterating `bip44.account`/`bip44.address_index` from a single
+    /// mnemonic is how HD wallet UIs discover additional accounts without building and
+    /// re-parsing path strings by hand.
+    pub fn from_mnemonic_bip44(
+        mnemonic_words: &str,
+        passphrase: &str,
+        bip44: Bip44,
+        hrp: String,
+    ) -> Result<Wallet, Error> {
+        Wallet::from_mnemonic_with_passphrase(mnemonic_words, passphrase, bip44.to_string(), hrp)
+    }
+
+    /// Returns the raw BIP-39 entropy backing this wallet's mnemonic, useful to persist the
+    /// wallet without storing the human-readable phrase.
+    pub fn entropy(&self) -> Vec<u8> {
+        self.mnemonic.entropy().to_vec()
+    }
+
+    /// Returns the derivation path used to derive this wallet's keychain.
+    pub fn derivation_path(&self) -> &str {
+        &self.derivation_path
+    }
+
+    /// Returns the human readable part used to compute this wallet's bech32 address.
+    pub fn hrp(&self) -> &str {
+        &self.hrp
+    }
+
+    /// Verifies that `sig` is a valid Secp256k1 ECDSA signature over `msg` produced by the holder
+    /// of `pub_key` (SEC1-encoded, compressed or uncompressed). Mirrors OpenEthereum's
+    /// `ethkey::verify_public`.
+    ///
+    /// # Errors
+    /// Returns [`Error::Verify`] if `pub_key` or `sig` aren't validly encoded.
+    pub fn verify(msg: &[u8], sig: &[u8], pub_key: &[u8]) -> Result<bool, Error> {
+        let verifying_key = VerifyingKey::from_sec1_bytes(pub_key)
+            .map_err(|err| Error::Verify(err.to_string()))?;
+        let signature = Signature::try_from(sig).map_err(|err| Error::Verify(err.to_string()))?;
+
+        Ok(verifying_key.verify(msg, &signature).is_ok())
+    }
+
     pub fn sign_tx(
         &self,
         account: BaseAccount,
@@ -169,6 +280,522 @@ impl Wallet {
 
         Ok(tx_signed_bytes)
     }
+
+    /// Like [`Wallet::sign_tx`], but signs under `SIGN_MODE_LEGACY_AMINO_JSON` instead of
+    /// `SIGN_MODE_DIRECT`. Ledger and other hardware-constrained signers can't display protobuf
+    /// sign docs, so they require the canonical `StdSignDoc` JSON document this signs instead;
+    /// the resulting tx's `body`/`auth_info` still carry the usual protobuf `TxBody`/`AuthInfo`
+    /// built from `msgs`, only the signature itself is computed over the Amino JSON document.
+    ///
+    /// Amino JSON has no equivalent to protobuf's `Any.type_url` scheme, so `amino_msgs` is the
+    /// per-message amino-type registry the caller populates: it must list the Amino JSON
+    /// encoding of each message in `msgs`, in the same order, tagged with its Amino type (e.g.
+    /// `"cosmos-sdk/MsgSend"`).
+    ///
+    /// # Errors
+    /// Returns [`Error::Encoding`] if `msgs`/`amino_msgs` don't serialize, or if `msgs.len() !=
+    /// amino_msgs.len()`.
+    pub fn sign_tx_amino(
+        &self,
+        account: BaseAccount,
+        chain_client: ChainClient,
+        msgs: &[Msg],
+        amino_msgs: &[AminoMsg],
+        fee: Fee,
+        memo: Option<String>,
+        timeout_height: u64,
+    ) -> Result<Vec<u8>, Error> {
+        if msgs.len() != amino_msgs.len() {
+            return Err(Error::Encoding(format!(
+                "expected {} amino message(s), got {}",
+                msgs.len(),
+                amino_msgs.len()
+            )));
+        }
+
+        let memo = memo.unwrap_or_default();
+
+        let tx_body = TxBody {
+            messages: msgs.iter().map(|msg| msg.0.clone()).collect(),
+            memo: memo.clone(),
+            timeout_height,
+            extension_options: Vec::<Any>::new(),
+            non_critical_extension_options: Vec::<Any>::new(),
+        };
+
+        let mut tx_body_buffer = Vec::new();
+        prost::Message::encode(&tx_body, &mut tx_body_buffer)
+            .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        // Canonical JSON: lexicographically sorted keys (the field declaration order below) and
+        // no insignificant whitespace, so every signer reproduces identical bytes.
+        let std_sign_doc = StdSignDoc {
+            account_number: account.account_number.to_string(),
+            chain_id: chain_client.node_info.id,
+            fee: StdFee {
+                amount: fee
+                    .amount
+                    .iter()
+                    .map(|coin| StdCoin {
+                        amount: coin.amount.clone(),
+                        denom: coin.denom.clone(),
+                    })
+                    .collect(),
+                gas: fee.gas_limit.to_string(),
+            },
+            memo,
+            msgs: amino_msgs.to_vec(),
+            sequence: account.sequence.to_string(),
+        };
+
+        let sign_doc_buffer = serde_json::to_vec(&std_sign_doc)
+            .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        // sha256-hashing happens implicitly: k256's `Signer<Signature>` impl hashes with SHA-256
+        // before producing the ECDSA signature, same as `sign_bytes` does for SIGN_MODE_DIRECT.
+        let signature: Signature =
+            sign_bytes(self.keychain.ext_private_key, sign_doc_buffer);
+
+        let mut pk_buffer = Vec::new();
+        prost::Message::encode(
+            &self.keychain.ext_public_key.public_key.to_bytes(),
+            &mut pk_buffer,
+        )
+        .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        let public_key_any = Any {
+            type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+            value: pk_buffer,
+        };
+
+        // SIGN_MODE_LEGACY_AMINO_JSON
+        let signer_info = SignerInfo {
+            public_key: Some(public_key_any),
+            mode_info: Some(ModeInfo {
+                sum: Some(Sum::Single(Single { mode: 127 })),
+            }),
+            sequence: account.sequence,
+        };
+
+        let auth_info = AuthInfo {
+            signer_infos: vec![signer_info],
+            fee: Some(fee),
+        };
+
+        let mut auth_buffer = Vec::new();
+        prost::Message::encode(&auth_info, &mut auth_buffer)
+            .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        let tx_raw = TxRaw {
+            body_bytes: tx_body_buffer,
+            auth_info_bytes: auth_buffer,
+            signatures: vec![signature.as_ref().to_vec()],
+        };
+
+        let mut tx_signed_bytes = Vec::new();
+        prost::Message::encode(&tx_raw, &mut tx_signed_bytes)
+            .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        Ok(tx_signed_bytes)
+    }
+}
+
+/// A message registered for `SIGN_MODE_LEGACY_AMINO_JSON` signing via [`Wallet::sign_tx_amino`].
+/// Amino JSON has no equivalent to protobuf's `Any.type_url` scheme, so each message carries its
+/// own Amino type tag (e.g. `"cosmos-sdk/MsgSend"`) alongside its JSON-encoded value.
+#[derive(serde::Serialize, Clone)]
+pub struct AminoMsg {
+    #[serde(rename = "type")]
+    amino_type: String,
+    value: serde_json::Value,
+}
+
+impl AminoMsg {
+    pub fn new(amino_type: &str, value: serde_json::Value) -> AminoMsg {
+        AminoMsg {
+            amino_type: amino_type.to_owned(),
+            value,
+        }
+    }
+}
+
+/// A `Coin` as it appears in a [`StdFee`], with its fields in lexicographic order (`amount`,
+/// `denom`) rather than `Coin`'s own `denom`, `amount`.
+#[derive(serde::Serialize)]
+struct StdCoin {
+    amount: String,
+    denom: String,
+}
+
+/// The `fee` field of a [`StdSignDoc`], mirroring the Cosmos SDK's `StdFee`.
+#[derive(serde::Serialize)]
+struct StdFee {
+    amount: Vec<StdCoin>,
+    gas: String,
+}
+
+/// The canonical JSON document signed under `SIGN_MODE_LEGACY_AMINO_JSON` (the Cosmos SDK's
+/// `StdSignDoc`), produced by [`Wallet::sign_tx_amino`]. Field names are declared here in
+/// lexicographic order, which `serde_json` preserves, so every signer serializes identical bytes
+/// for the same inputs. `msgs` keeps the order of the `amino_msgs` it was built from.
+#[derive(serde::Serialize)]
+struct StdSignDoc {
+    account_number: String,
+    chain_id: String,
+    fee: StdFee,
+    memo: String,
+    msgs: Vec<AminoMsg>,
+    sequence: String,
+}
+
+/// A single signer's signature together with the public key needed to verify it, produced by
+/// [`sign_sign_doc`] and consumed by [`assemble_tx`].
+pub struct TxSignature {
+    pub public_key: Any,
+    pub signature: Vec<u8>,
+}
+
+/// Builds the canonical [`SignDoc`] bytes for a transaction, without touching any private key.
+///
+/// This is the first of three stages (`build_sign_doc` -> [`sign_sign_doc`] -> [`assemble_tx`])
+/// that let a watch-only device prepare a transaction, an offline signer produce a signature
+/// over it, and a third party broadcast the result, without any single party needing access to
+/// both the network and the private key.
+///
+/// `account` must already carry the public key of the signer (or, for a multisig account, of
+/// the multisig itself), since that public key has to be embedded in the `AuthInfo` that gets
+/// signed over.
+pub fn build_sign_doc(
+    account: &BaseAccount,
+    node_info: &NodeInfo,
+    msgs: &[Msg],
+    fee: Fee,
+    memo: Option<String>,
+) -> Result<Vec<u8>, Error> {
+    let memo = memo.unwrap_or_default();
+
+    let tx_body = TxBody {
+        messages: msgs.iter().map(|msg| msg.0.clone()).collect(),
+        memo,
+        timeout_height: 0,
+        extension_options: Vec::<Any>::new(),
+        non_critical_extension_options: Vec::<Any>::new(),
+    };
+
+    let mut tx_body_buffer = Vec::new();
+    prost::Message::encode(&tx_body, &mut tx_body_buffer)
+        .map_err(|err| Error::Encoding(err.to_string()))?;
+
+    let public_key_any = account
+        .pub_key
+        .clone()
+        .ok_or_else(|| Error::Encoding("account has no public key".to_string()))?;
+
+    let single_signer = Single { mode: 1 };
+    let signer_info = SignerInfo {
+        public_key: Some(public_key_any),
+        mode_info: Some(ModeInfo {
+            sum: Some(Sum::Single(single_signer)),
+        }),
+        sequence: account.sequence,
+    };
+
+    let auth_info = AuthInfo {
+        signer_infos: vec![signer_info],
+        fee: Some(fee),
+    };
+
+    let mut auth_buffer = Vec::new();
+    prost::Message::encode(&auth_info, &mut auth_buffer)
+        .map_err(|err| Error::Encoding(err.to_string()))?;
+
+    let sign_doc = SignDoc {
+        body_bytes: tx_body_buffer,
+        auth_info_bytes: auth_buffer,
+        chain_id: node_info.id.clone(),
+        account_number: account.account_number,
+    };
+
+    let mut sign_doc_buffer = Vec::new();
+    prost::Message::encode(&sign_doc, &mut sign_doc_buffer)
+        .map_err(|err| Error::Encoding(err.to_string()))?;
+
+    Ok(sign_doc_buffer)
+}
+
+/// Signs the bytes produced by [`build_sign_doc`] with `wallet`'s private key, returning the
+/// signature together with the public key needed to verify it. This never needs network access,
+/// so it can run entirely on an air-gapped device.
+pub fn sign_sign_doc(wallet: &Wallet, sign_doc_bytes: &[u8]) -> Result<TxSignature, Error> {
+    let mut pk_buffer = Vec::new();
+    prost::Message::encode(
+        &wallet.keychain.ext_public_key.public_key.to_bytes(),
+        &mut pk_buffer,
+    )
+    .map_err(|err| Error::Encoding(err.to_string()))?;
+
+    let public_key = Any {
+        type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+        value: pk_buffer,
+    };
+
+    let signature: Signature = sign_bytes(wallet.keychain.ext_private_key, sign_doc_bytes.to_vec());
+
+    Ok(TxSignature {
+        public_key,
+        signature: signature.as_ref().to_vec(),
+    })
+}
+
+/// Assembles broadcast-ready [`TxRaw`] bytes from a sign doc and one or more signatures,
+/// collected from [`sign_sign_doc`], without needing the private key that produced them.
+///
+/// Accepting more than one [`TxSignature`] lets this naturally extend to multisig accounts and
+/// joint transactions where several independent signers each attach their own signature, in the
+/// same order as the signer infos embedded in the sign doc. Each signature's public key is
+/// checked against the matching signer info before assembly, to catch misordered signatures.
+pub fn assemble_tx(sign_doc_bytes: &[u8], signatures: &[TxSignature]) -> Result<Vec<u8>, Error> {
+    let sign_doc: SignDoc = prost::Message::decode(sign_doc_bytes)
+        .map_err(|err| Error::Decode(err.to_string()))?;
+
+    let auth_info: AuthInfo = prost::Message::decode(sign_doc.auth_info_bytes.as_slice())
+        .map_err(|err| Error::Decode(err.to_string()))?;
+
+    if auth_info.signer_infos.len() != signatures.len() {
+        return Err(Error::Encoding(format!(
+            "expected {} signature(s), got {}",
+            auth_info.signer_infos.len(),
+            signatures.len()
+        )));
+    }
+
+    for (signer_info, tx_signature) in auth_info.signer_infos.iter().zip(signatures) {
+        if signer_info.public_key.as_ref() != Some(&tx_signature.public_key) {
+            return Err(Error::Encoding(
+                "signature public key does not match the signer info in the sign doc".to_string(),
+            ));
+        }
+    }
+
+    let tx_raw = TxRaw {
+        body_bytes: sign_doc.body_bytes,
+        auth_info_bytes: sign_doc.auth_info_bytes,
+        signatures: signatures.iter().map(|s| s.signature.clone()).collect(),
+    };
+
+    let mut tx_signed_bytes = Vec::new();
+    prost::Message::encode(&tx_raw, &mut tx_signed_bytes)
+        .map_err(|err| Error::Encoding(err.to_string()))?;
+
+    Ok(tx_signed_bytes)
+}
+
+/// Assembles a `TxRaw` signed by a legacy Amino threshold multisig account, whose `SignerInfo`
+/// [`Wallet::sign_tx`] cannot build since it only ever produces a single, non-multisig signer.
+///
+/// Each member signs `sign_doc_bytes` (e.g. produced by [`build_sign_doc`] against an `account`
+/// whose `pub_key` is this multisig's `/cosmos.crypto.multisig.LegacyAminoPubKey`) independently
+/// and offline, and hands their raw signature back to [`MultiSigTx::collect_signature`]; once at
+/// least `threshold` of them are in, [`MultiSigTx::finalize`] assembles the final `TxRaw`.
+pub struct MultiSigTx {
+    sign_doc_bytes: Vec<u8>,
+    member_pub_keys: Vec<Vec<u8>>,
+    threshold: u32,
+    signatures: std::collections::BTreeMap<usize, Vec<u8>>,
+}
+
+impl MultiSigTx {
+    /// Starts assembling a multisig tx over `sign_doc_bytes`, signed by the threshold multisig
+    /// made up of `member_pub_keys` (SEC1-encoded Secp256k1 public keys, in the multisig's own
+    /// member order) requiring `threshold` signatures.
+    pub fn new(sign_doc_bytes: Vec<u8>, member_pub_keys: Vec<Vec<u8>>, threshold: u32) -> MultiSigTx {
+        MultiSigTx {
+            sign_doc_bytes,
+            member_pub_keys,
+            threshold,
+            signatures: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// Records `signature` as having come from the member at `signer_index` into
+    /// [`MultiSigTx::new`]'s `member_pub_keys`, so it can be gathered incrementally as co-signers
+    /// sign offline and send their signature back, in no particular order.
+    pub fn collect_signature(mut self, signer_index: usize, signature: Vec<u8>) -> Self {
+        self.signatures.insert(signer_index, signature);
+        self
+    }
+
+    /// Assembles the final `TxRaw`, whose single `SignerInfo` carries a
+    /// `/cosmos.crypto.multisig.LegacyAminoPubKey` built from `member_pub_keys`/`threshold`, a
+    /// `ModeInfo` of `Sum::Multi` with a `CompactBitArray` marking which members signed, and the
+    /// concatenated member signatures in member order.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotEnoughSignatures`] if fewer than `threshold` signatures have been
+    /// collected.
+    pub fn finalize(self) -> Result<Vec<u8>, Error> {
+        if self.signatures.len() < self.threshold as usize {
+            return Err(Error::NotEnoughSignatures(format!(
+                "{} of {} required signatures collected",
+                self.signatures.len(),
+                self.threshold
+            )));
+        }
+
+        let sign_doc: SignDoc = prost::Message::decode(self.sign_doc_bytes.as_slice())
+            .map_err(|err| Error::Decode(err.to_string()))?;
+        let fee = prost::Message::decode(sign_doc.auth_info_bytes.as_slice())
+            .map_err(|err| Error::Decode(err.to_string()))
+            .map(|auth_info: AuthInfo| auth_info.fee)?;
+
+        let public_keys = self
+            .member_pub_keys
+            .iter()
+            .map(|pub_key| Any {
+                type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+                value: pub_key.clone(),
+            })
+            .collect();
+
+        let mut multisig_pub_key_buffer = Vec::new();
+        prost::Message::encode(
+            &LegacyAminoPubKey {
+                threshold: self.threshold,
+                public_keys,
+            },
+            &mut multisig_pub_key_buffer,
+        )
+        .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        let public_key_any = Any {
+            type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_string(),
+            value: multisig_pub_key_buffer,
+        };
+
+        let extra_bits_stored = (self.member_pub_keys.len() % 8) as u32;
+        let mut elems = vec![0u8; (self.member_pub_keys.len() + 7) / 8];
+        for &signer_index in self.signatures.keys() {
+            elems[signer_index / 8] |= 0x80 >> (signer_index % 8);
+        }
+
+        let mode_infos = self
+            .signatures
+            .keys()
+            .map(|_| ModeInfo {
+                sum: Some(Sum::Single(Single { mode: 1 })),
+            })
+            .collect();
+
+        let signer_info = SignerInfo {
+            public_key: Some(public_key_any),
+            mode_info: Some(ModeInfo {
+                sum: Some(Sum::Multi(Multi {
+                    bitarray: Some(CompactBitArray {
+                        extra_bits_stored,
+                        elems,
+                    }),
+                    mode_infos,
+                })),
+            }),
+            sequence: 0,
+        };
+
+        let auth_info = AuthInfo {
+            signer_infos: vec![signer_info],
+            fee,
+        };
+
+        let mut auth_info_buffer = Vec::new();
+        prost::Message::encode(&auth_info, &mut auth_info_buffer)
+            .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        // The concatenated member signatures, in ascending signer-index order, form the single
+        // `TxRaw` signature slot a legacy Amino multisig account expects.
+        let signature: Vec<u8> = self.signatures.into_values().flatten().collect();
+
+        let tx_raw = TxRaw {
+            body_bytes: sign_doc.body_bytes,
+            auth_info_bytes: auth_info_buffer,
+            signatures: vec![signature],
+        };
+
+        let mut tx_signed_bytes = Vec::new();
+        prost::Message::encode(&tx_raw, &mut tx_signed_bytes)
+            .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        Ok(tx_signed_bytes)
+    }
+}
+
+/// Recovers the bech32 address, under `hrp`, of the Secp256k1 key that produced
+/// `recoverable_sig` over `msg`. Mirrors OpenEthereum's `ethkey::brain_recover`, recast for
+/// Cosmos bech32 addresses instead of Ethereum's keccak-derived ones.
+///
+/// `recoverable_sig` is a 65-byte recoverable ECDSA signature: a 64-byte `r || s` signature
+/// followed by a 1-byte recovery id, the format produced by [`k256::ecdsa::recoverable::Signature`].
+///
+/// # Errors
+/// Returns [`Error::Verify`] if `recoverable_sig` is malformed or no public key can be recovered
+/// from it, or [`Error::Bech32`] if `hrp` is invalid.
+pub fn recover_address(msg: &[u8], recoverable_sig: &[u8], hrp: &str) -> Result<String, Error> {
+    let signature = recoverable::Signature::try_from(recoverable_sig)
+        .map_err(|err| Error::Verify(err.to_string()))?;
+
+    let verify_key = signature
+        .recover_verify_key(msg)
+        .map_err(|err| Error::Verify(err.to_string()))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(verify_key.to_bytes().as_slice());
+    let pk_hash = hasher.finalize();
+
+    let mut rip_hasher = Ripemd160::new();
+    rip_hasher.update(pk_hash);
+    let address_bytes = rip_hasher.finalize().to_vec();
+
+    bech32::encode(hrp, address_bytes.to_base32(), Bech32).map_err(|err| Error::Bech32(err.to_string()))
+}
+
+/// Verifies a signed transaction previously produced by [`Wallet::sign_tx`] and encoded into raw
+/// `TxRaw` bytes: reconstructs the `SignDoc` the transaction was signed over from the decoded
+/// `TxRaw`, then checks the embedded signature against the embedded `SignerInfo.public_key`. Lets
+/// a caller validate a broadcast-ready tx before sending it.
+///
+/// # Errors
+/// Returns an [`Err`] if one of the following cases:
+/// * If `tx_bytes` isn't a validly encoded `TxRaw`, or its `auth_info_bytes` isn't a validly
+///   encoded `AuthInfo`.
+/// * If the `TxRaw` carries no signer info, no public key, or no signature.
+pub fn verify_tx_raw(tx_bytes: &[u8], chain_id: String, account_number: u64) -> Result<bool, Error> {
+    let tx_raw: TxRaw =
+        prost::Message::decode(tx_bytes).map_err(|err| Error::Decode(err.to_string()))?;
+    let auth_info: AuthInfo = prost::Message::decode(tx_raw.auth_info_bytes.as_slice())
+        .map_err(|err| Error::Decode(err.to_string()))?;
+
+    let public_key_any = auth_info
+        .signer_infos
+        .first()
+        .and_then(|signer_info| signer_info.public_key.as_ref())
+        .ok_or_else(|| Error::Verify("tx has no signer public key".to_string()))?;
+    let pub_key: Vec<u8> = prost::Message::decode(public_key_any.value.as_slice())
+        .map_err(|err| Error::Decode(err.to_string()))?;
+
+    let signature = tx_raw
+        .signatures
+        .first()
+        .ok_or_else(|| Error::Verify("tx has no signature".to_string()))?;
+
+    let sign_doc = SignDoc {
+        body_bytes: tx_raw.body_bytes,
+        auth_info_bytes: tx_raw.auth_info_bytes,
+        chain_id,
+        account_number,
+    };
+    let mut sign_doc_buffer = Vec::new();
+    prost::Message::encode(&sign_doc, &mut sign_doc_buffer)
+        .map_err(|err| Error::Encoding(err.to_string()))?;
+
+    Wallet::verify(&sign_doc_buffer, signature, &pub_key)
 }
 
 /// generate a keychain of Secp256k1 keys from the given hd_path and seed.
@@ -216,6 +843,88 @@ fn bech32_address_from_public_key(pub_key: ExtendedPubKey, hrp: String) -> Resul
 }
 
 
+/// A pattern matched against a bech32 address's data part - everything after the hrp's `1`
+/// separator - by [`generate_vanity`]. Mirrors the prefix/suffix/charset knobs OpenEthereum's
+/// `ethkey` exposes via `BrainPrefix`/`Prefix` for Ethereum vanity keys, recast for Cosmos bech32
+/// addresses.
+pub enum VanityPattern {
+    /// Matches addresses whose data part starts with this literal string.
+    Prefix(String),
+    /// Matches addresses whose data part ends with this literal string.
+    Suffix(String),
+    /// Matches addresses whose data part is made up entirely of characters from this set.
+    Charset(String),
+}
+
+impl VanityPattern {
+    fn matches(&self, data_part: &str) -> bool {
+        match self {
+            VanityPattern::Prefix(prefix) => data_part.starts_with(prefix.as_str()),
+            VanityPattern::Suffix(suffix) => data_part.ends_with(suffix.as_str()),
+            VanityPattern::Charset(charset) => data_part.chars().all(|c| charset.contains(c)),
+        }
+    }
+}
+
+/// The result of a successful [`generate_vanity`] search: the matching wallet, together with the
+/// `address_index` it was derived at so the same address can be reproduced later from just the
+/// mnemonic and this index, instead of storing a new key.
+pub struct VanityMatch {
+    pub wallet: Wallet,
+    pub address_index: u32,
+}
+
+/// Searches the BIP-44 address-index space `m/44'/coin_type'/0'/0/i` for `i = 0, 1, 2, ...` for an
+/// account whose bech32 address under `hrp` matches `pattern`, deriving each candidate via
+/// [`Wallet::from_mnemonic_bip44`] and testing `pattern` against the data part following the
+/// `hrp`'s `1` separator.
+///
+/// This crate targets wasm, which has no threads to spread the search across, so it runs
+/// sequentially on the calling one; native callers wanting a parallel search should reach for
+/// [`crw_wallet`](https://docs.rs/crw-wallet)'s `MnemonicWallet::generate_vanity` instead, which
+/// spreads the same search across a thread pool.
+///
+/// # Errors
+/// Returns [`Error::Vanity`] if no address matching `pattern` turns up within `max_attempts`
+/// derivations.
+pub fn generate_vanity(
+    mnemonic_words: &str,
+    passphrase: &str,
+    coin_type: u32,
+    hrp: &str,
+    pattern: &VanityPattern,
+    max_attempts: u32,
+) -> Result<VanityMatch, Error> {
+    for address_index in 0..max_attempts {
+        let bip44 = Bip44 {
+            coin_type,
+            account: 0,
+            change: 0,
+            address_index,
+        };
+
+        let wallet =
+            Wallet::from_mnemonic_bip44(mnemonic_words, passphrase, bip44, hrp.to_string())?;
+
+        let data_part = wallet
+            .bech32_address
+            .split_once('1')
+            .map_or("", |(_, data)| data);
+
+        if pattern.matches(data_part) {
+            return Ok(VanityMatch {
+                wallet,
+                address_index,
+            });
+        }
+    }
+
+    Err(Error::Vanity(format!(
+        "no address matching the given pattern found within {} attempts",
+        max_attempts
+    )))
+}
+
 /// sign the given bytes with the given private key, returning a signature representation
 fn sign_bytes(ext_private_key: ExtendedPrivKey, bytes_to_sign: Vec<u8>) -> Signature {
     let private_key_bytes = ext_private_key.private_key.to_bytes();
@@ -303,6 +1012,101 @@ mod tests {
         )
     }
 
+    #[test]
+    fn from_mnemonic_with_passphrase_differs_from_no_passphrase() {
+        let mnemonic_words = "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel";
+
+        let without_passphrase = Wallet::from_mnemonic(
+            mnemonic_words,
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        let with_passphrase = Wallet::from_mnemonic_with_passphrase(
+            mnemonic_words,
+            "some passphrase",
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        assert_ne!(without_passphrase.bech32_address, with_passphrase.bech32_address);
+    }
+
+    #[test]
+    fn from_mnemonic_bip44_matches_manual_path() {
+        let mnemonic_words = "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel";
+
+        let manual = Wallet::from_mnemonic(
+            mnemonic_words,
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        let structured = Wallet::from_mnemonic_bip44(
+            mnemonic_words,
+            "",
+            Bip44 { coin_type: 852, account: 0, change: 0, address_index: 0 },
+            "desmos".to_string(),
+        ).unwrap();
+
+        assert_eq!(manual.bech32_address, structured.bech32_address);
+    }
+
+    #[test]
+    fn from_mnemonic_rejects_a_malformed_derivation_path_instead_of_panicking() {
+        let result = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "not a derivation path".to_string(),
+            "desmos".to_string(),
+        );
+
+        assert!(matches!(result, Err(Error::DerivationPath(_))));
+    }
+
+    #[test]
+    fn bip44_to_hd_path_matches_the_formatted_string() {
+        let bip44 = Bip44 { coin_type: 852, account: 0, change: 0, address_index: 3 };
+
+        assert_eq!(bip44.to_string(), "m/44'/852'/0'/0/3");
+        assert!(bip44.to_hd_path().is_ok());
+    }
+
+    #[test]
+    fn generate_vanity_finds_a_prefix_match() {
+        let mnemonic_words = "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel";
+
+        let found = generate_vanity(
+            mnemonic_words,
+            "",
+            852,
+            "desmos",
+            &VanityPattern::Prefix("k8u".to_string()),
+            10,
+        ).unwrap();
+
+        assert_eq!(found.address_index, 0);
+        assert_eq!(
+            found.wallet.bech32_address,
+            "desmos1k8u92hx3k33a5vgppkyzq6m4frxx7ewnlkyjrh"
+        );
+    }
+
+    #[test]
+    fn generate_vanity_fails_when_attempts_are_exhausted() {
+        let mnemonic_words = "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel";
+
+        let result = generate_vanity(
+            mnemonic_words,
+            "",
+            852,
+            "desmos",
+            &VanityPattern::Prefix("impossiblylongprefixnoaddresswillever".to_string()),
+            3,
+        );
+
+        assert!(matches!(result, Err(Error::Vanity(_))));
+    }
+
     #[test]
     fn sign_bytes_works() {
         let wallet = Wallet::from_mnemonic(
@@ -331,6 +1135,253 @@ mod tests {
         assert!(verify_key.verify(msg_bytes.clone().as_slice(), &signature).is_ok());
     }
 
+    #[test]
+    fn verify_accepts_a_valid_signature_and_rejects_a_tampered_one() {
+        let wallet = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        let data = "some simple data".as_bytes();
+        let signature = sign_bytes(wallet.keychain.ext_private_key.clone(), data.to_vec());
+        let pub_key = wallet.keychain.ext_public_key.public_key.to_bytes();
+
+        assert!(Wallet::verify(data, signature.as_ref(), &pub_key).unwrap());
+        assert!(!Wallet::verify(b"other data", signature.as_ref(), &pub_key).unwrap());
+    }
+
+    #[test]
+    fn verify_rejects_a_malformed_public_key() {
+        let wallet = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        let data = "some simple data".as_bytes();
+        let signature = sign_bytes(wallet.keychain.ext_private_key, data.to_vec());
+
+        let result = Wallet::verify(data, signature.as_ref(), &[0u8; 4]);
+
+        assert!(matches!(result, Err(Error::Verify(_))));
+    }
+
+    #[test]
+    fn multi_sig_tx_assembles_a_legacy_amino_multisig_signer_info() {
+        let member_a = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+        let member_b = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "m/44'/852'/0'/0/1".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+        let member_pub_keys = vec![
+            member_a.keychain.ext_public_key.public_key.to_bytes(),
+            member_b.keychain.ext_public_key.public_key.to_bytes(),
+        ];
+
+        let node_info = NodeInfo { id: "testchain".to_string(), network: "testchain".to_string() };
+        let fee = Fee {
+            amount: vec![Coin { denom: "stake".to_string(), amount: "5000".to_string() }],
+            gas_limit: 300000,
+            payer: "".to_string(),
+            granter: "".to_string(),
+        };
+        let multisig_account = BaseAccount {
+            address: "desmos1multisigplaceholder".to_string(),
+            pub_key: Some(Any { type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_string(), value: vec![] }),
+            account_number: 1,
+            sequence: 5,
+        };
+        let sign_doc_bytes = build_sign_doc(&multisig_account, &node_info, &[], fee, None).unwrap();
+
+        let signature_a = sign_sign_doc(&member_a, &sign_doc_bytes).unwrap().signature;
+        let signature_b = sign_sign_doc(&member_b, &sign_doc_bytes).unwrap().signature;
+
+        let too_few = MultiSigTx::new(sign_doc_bytes.clone(), member_pub_keys.clone(), 2)
+            .collect_signature(0, signature_a.clone())
+            .finalize();
+        assert!(matches!(too_few, Err(Error::NotEnoughSignatures(_))));
+
+        let tx_bytes = MultiSigTx::new(sign_doc_bytes, member_pub_keys, 2)
+            .collect_signature(0, signature_a.clone())
+            .collect_signature(1, signature_b.clone())
+            .finalize()
+            .unwrap();
+
+        let tx_raw: TxRaw = prost::Message::decode(tx_bytes.as_slice()).unwrap();
+        let auth_info: AuthInfo = prost::Message::decode(tx_raw.auth_info_bytes.as_slice()).unwrap();
+        let signer_info = &auth_info.signer_infos[0];
+
+        assert_eq!(
+            "/cosmos.crypto.multisig.LegacyAminoPubKey",
+            signer_info.public_key.as_ref().unwrap().type_url
+        );
+        let mode_info = signer_info.mode_info.as_ref().unwrap();
+        assert!(matches!(mode_info.sum, Some(Sum::Multi(_))));
+        assert_eq!([signature_a, signature_b].concat(), tx_raw.signatures[0]);
+    }
+
+    #[test]
+    fn recover_address_finds_the_signer() {
+        let wallet = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        let data = "some simple data".as_bytes();
+        let signing_key =
+            SigningKey::from_bytes(wallet.keychain.ext_private_key.private_key.to_bytes().as_slice())
+                .unwrap();
+        let signature: recoverable::Signature = signing_key.try_sign(data).unwrap();
+
+        let address = recover_address(data, signature.as_ref(), "desmos").unwrap();
+
+        assert_eq!(wallet.bech32_address, address);
+    }
+
+    #[test]
+    fn verify_tx_raw_accepts_a_tx_this_wallet_signed_and_rejects_a_mismatched_chain_id() {
+        let wallet = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        let mut pk_buffer = Vec::new();
+        prost::Message::encode(
+            &wallet.keychain.ext_public_key.public_key.to_bytes(),
+            &mut pk_buffer,
+        ).unwrap();
+        let account = BaseAccount {
+            address: wallet.bech32_address.clone(),
+            pub_key: Some(Any {
+                type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+                value: pk_buffer,
+            }),
+            account_number: 1,
+            sequence: 5,
+        };
+        let node_info = NodeInfo { id: "testchain".to_string(), network: "testchain".to_string() };
+
+        let fee = Fee {
+            amount: vec![Coin { denom: "stake".to_string(), amount: "5000".to_string() }],
+            gas_limit: 300000,
+            payer: "".to_string(),
+            granter: "".to_string(),
+        };
+
+        let sign_doc_bytes = build_sign_doc(&account, &node_info, &[], fee, None).unwrap();
+        let signature = sign_sign_doc(&wallet, &sign_doc_bytes).unwrap();
+        let tx_bytes = assemble_tx(&sign_doc_bytes, &[signature]).unwrap();
+
+        assert!(verify_tx_raw(&tx_bytes, "testchain".to_string(), 1).unwrap());
+        assert!(!verify_tx_raw(&tx_bytes, "otherchain".to_string(), 1).unwrap());
+    }
+
+    #[test]
+    fn sign_tx_amino_sets_the_legacy_amino_json_sign_mode() {
+        let wallet = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        let node_info = NodeInfo { id: "testchain".to_string(), network: "testchain".to_string() };
+        let chain_client = ChainClient::new(node_info, "http://localhost:1317".to_string(), "http://localhost:9090".to_string());
+
+        let account = BaseAccount {
+            address: wallet.bech32_address.clone(),
+            pub_key: None,
+            account_number: 1,
+            sequence: 5,
+        };
+
+        let amount = Coin { denom: "stake".to_string(), amount: "100000".to_string() };
+        let msg = MsgSend {
+            from_address: wallet.bech32_address.clone(),
+            to_address: "desmos1gvd8j8w986qey68s6trc3h9zkzxest20zs5g0w".to_string(),
+            amount: vec![amount],
+        };
+        let mut msg_bytes = Vec::new();
+        prost::Message::encode(&msg, &mut msg_bytes).unwrap();
+        let proto_msg = Msg::new("/cosmos.bank.v1beta1.Msg/Send", msg_bytes);
+
+        let amino_msg = AminoMsg::new(
+            "cosmos-sdk/MsgSend",
+            serde_json::json!({
+                "from_address": wallet.bech32_address,
+                "to_address": "desmos1gvd8j8w986qey68s6trc3h9zkzxest20zs5g0w",
+                "amount": [{"denom": "stake", "amount": "100000"}],
+            }),
+        );
+
+        let fee = Fee {
+            amount: vec![Coin { denom: "stake".to_string(), amount: "5000".to_string() }],
+            gas_limit: 300000,
+            payer: "".to_string(),
+            granter: "".to_string(),
+        };
+
+        let tx_signed_bytes = wallet
+            .sign_tx_amino(account, chain_client, &[proto_msg], &[amino_msg], fee, None, 0)
+            .unwrap();
+
+        let tx_raw: TxRaw = prost::Message::decode(tx_signed_bytes.as_slice()).unwrap();
+        let auth_info: AuthInfo =
+            prost::Message::decode(tx_raw.auth_info_bytes.as_slice()).unwrap();
+
+        assert_ne!(tx_raw.signatures[0].len(), 0);
+
+        let mode_info = auth_info.signer_infos[0].mode_info.as_ref().unwrap();
+        assert_eq!(Some(Sum::Single(Single { mode: 127 })), mode_info.sum);
+    }
+
+    #[test]
+    fn sign_tx_amino_rejects_a_mismatched_message_count() {
+        let wallet = Wallet::from_mnemonic(
+            "battle call once stool three mammal hybrid list sign field athlete amateur cinnamon eagle shell erupt voyage hero assist maple matrix maximum able barrel",
+            "m/44'/852'/0'/0/0".to_string(),
+            "desmos".to_string(),
+        ).unwrap();
+
+        let node_info = NodeInfo { id: "testchain".to_string(), network: "testchain".to_string() };
+        let chain_client = ChainClient::new(node_info, "http://localhost:1317".to_string(), "http://localhost:9090".to_string());
+
+        let account = BaseAccount {
+            address: wallet.bech32_address.clone(),
+            pub_key: None,
+            account_number: 1,
+            sequence: 5,
+        };
+
+        let fee = Fee {
+            amount: vec![Coin { denom: "stake".to_string(), amount: "5000".to_string() }],
+            gas_limit: 300000,
+            payer: "".to_string(),
+            granter: "".to_string(),
+        };
+
+        let amount = Coin { denom: "stake".to_string(), amount: "100000".to_string() };
+        let msg = MsgSend {
+            from_address: wallet.bech32_address.clone(),
+            to_address: "desmos1gvd8j8w986qey68s6trc3h9zkzxest20zs5g0w".to_string(),
+            amount: vec![amount],
+        };
+        let mut msg_bytes = Vec::new();
+        prost::Message::encode(&msg, &mut msg_bytes).unwrap();
+        let proto_msg = Msg::new("/cosmos.bank.v1beta1.Msg/Send", msg_bytes);
+
+        let result = wallet.sign_tx_amino(account, chain_client, &[proto_msg], &[], fee, None, 0);
+
+        assert!(matches!(result, Err(Error::Encoding(_))));
+    }
+
     #[actix_rt::test]
     async fn sign_tx_works() {
         let wallet = Wallet::from_mnemonic(